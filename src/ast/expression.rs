@@ -30,6 +30,8 @@ impl Display for ExpressionValue<'_, Expression<'_>> {
                 arguments: _,
             } => write!(f, "calling {}", callee.value),
             ExpressionValue::Lambda(_) => write!(f, "lambda expression"),
+            ExpressionValue::Path(path) => write!(f, "`{}` path", path),
+            ExpressionValue::Interpolation(_) => write!(f, "interpolated string"),
         }
     }
 }
@@ -38,6 +40,8 @@ impl Display for Primitive<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Primitive::Integer(value) => write!(f, "{}", value),
+            Primitive::Int32(value) => write!(f, "{}i", value),
+            Primitive::Int64(value) => write!(f, "{}l", value),
             Primitive::Decimal(value) => write!(f, "{}", value),
             Primitive::String(value) => write!(f, "{}", value),
             Primitive::Identifier(value) => write!(f, "{}", value),
@@ -145,11 +149,47 @@ pub enum ExpressionValue<'ast, Expression> {
         arguments: Vec<Expression>,
     },
     Lambda(Lambda<'ast>),
+    Path(Path<'ast>),
+    /// A string with embedded expressions, such as `"x = ${x}"`, as its string fragments and
+    /// embedded expressions in order.
+    Interpolation(Vec<Expression>),
 }
 
+/// A name written in the source code, such as a segment of a path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Identifier<'ast> {
+    pub name: Cow<'ast, str>,
+    pub span: miette::SourceSpan,
+}
+
+/// A path of names separated by `::`, such as `std::print`. Every segment keeps its own span, so
+/// diagnostics can point at the one that failed to resolve.
 #[derive(Debug, Clone)]
+pub struct Path<'ast> {
+    pub segments: Vec<Identifier<'ast>>,
+    pub span: miette::SourceSpan,
+}
+
+impl Display for Path<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = self
+            .segments
+            .iter()
+            .map(|segment| segment.name.as_ref())
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", names.join("::"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Primitive<'ast> {
+    /// An integer literal without a suffix, whose width follows from how it is used.
     Integer(i64),
+    /// A 32-bit integer literal; `5i`.
+    Int32(i64),
+    /// A 64-bit integer literal; `5l`.
+    Int64(i64),
     Decimal(f64),
     String(Cow<'ast, str>),
     Identifier(Cow<'ast, str>),
@@ -158,7 +198,7 @@ pub enum Primitive<'ast> {
     Unit,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BinaryOperator {
     Add,
     Subtract,
@@ -188,7 +228,7 @@ impl BinaryOperator {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UnaryOperator {
     Negate,
     Negative,
@@ -197,5 +237,12 @@ pub enum UnaryOperator {
 #[derive(Debug, Clone)]
 pub struct Lambda<'ast> {
     pub parameters: Vec<ParameterDeclaration<'ast>>,
+    pub explicit_return_type: Option<Type<'ast>>,
     pub body: Box<Expression<'ast>>,
 }
+
+impl<'ast> Lambda<'ast> {
+    pub fn parameter_names(&self) -> Vec<Cow<'ast, str>> {
+        self.parameters.iter().map(|p| p.name.clone()).collect()
+    }
+}
@@ -0,0 +1,158 @@
+use super::{
+    ContainsSpan, Expression, ExpressionValue, Module, Position, Statement, StatementValue, Type,
+    TypedExpression, TypedStatement,
+};
+
+/// A node of the tree found at a position in the source code.
+#[derive(Debug, Clone, Copy)]
+pub enum Node<'a, 'ast> {
+    Statement(&'a Statement<'ast, Expression<'ast>>),
+    Expression(&'a Expression<'ast>),
+    TypedStatement(&'a TypedStatement<'ast>),
+    TypedExpression(&'a TypedExpression<'ast>),
+}
+
+impl<'ast> Node<'_, 'ast> {
+    pub fn span(&self) -> miette::SourceSpan {
+        match self {
+            Node::Statement(statement) => statement.span,
+            Node::Expression(expression) => expression.span,
+            Node::TypedStatement(statement) => statement.span,
+            Node::TypedExpression(expression) => expression.span,
+        }
+    }
+
+    /// The inferred type of the node. Only typed expressions carry one; the type checker does
+    /// not annotate the expressions nested inside them yet.
+    pub fn ty(&self) -> Option<&Type<'ast>> {
+        match self {
+            Node::TypedExpression(expression) => Some(&expression.ty),
+            _ => None,
+        }
+    }
+}
+
+/// Finds the innermost node covering `position`, such as the node under the cursor in an
+/// editor. When spans overlap, the smallest node wins.
+pub fn find_node_at<'a, 'ast>(
+    modules: &'a [Module<'ast, TypedExpression<'ast>>],
+    source: &str,
+    position: &Position,
+) -> Option<Node<'a, 'ast>> {
+    let offset = position.to_offset(source)?;
+
+    modules
+        .iter()
+        .flat_map(|module| &module.definitions)
+        .filter_map(|statement| find_in_statement(statement, offset))
+        .min_by_key(|node| node.span().len())
+}
+
+trait Locate<'ast>: Sized {
+    fn locate<'a>(&'a self, offset: usize) -> Option<Node<'a, 'ast>>;
+
+    fn statement_node<'a>(statement: &'a Statement<'ast, Self>) -> Node<'a, 'ast>;
+}
+
+impl<'ast> Locate<'ast> for Expression<'ast> {
+    fn locate<'a>(&'a self, offset: usize) -> Option<Node<'a, 'ast>> {
+        find_in_children(&self.value, offset)
+            .or_else(|| self.span.contains(offset).then_some(Node::Expression(self)))
+    }
+
+    fn statement_node<'a>(statement: &'a Statement<'ast, Self>) -> Node<'a, 'ast> {
+        Node::Statement(statement)
+    }
+}
+
+impl<'ast> Locate<'ast> for TypedExpression<'ast> {
+    fn locate<'a>(&'a self, offset: usize) -> Option<Node<'a, 'ast>> {
+        find_in_children(&self.value, offset).or_else(|| {
+            self.span
+                .contains(offset)
+                .then_some(Node::TypedExpression(self))
+        })
+    }
+
+    fn statement_node<'a>(statement: &'a Statement<'ast, Self>) -> Node<'a, 'ast> {
+        Node::TypedStatement(statement)
+    }
+}
+
+// Children are searched even when the parent does not cover the offset, since a block is
+// spanned by its return value rather than by its statements.
+fn find_in_children<'a, 'ast>(
+    value: &'a ExpressionValue<'ast, Expression<'ast>>,
+    offset: usize,
+) -> Option<Node<'a, 'ast>> {
+    match value {
+        ExpressionValue::Primitive(_) | ExpressionValue::Path(_) => None,
+        ExpressionValue::Binary { left, right, .. } => {
+            left.locate(offset).or_else(|| right.locate(offset))
+        }
+        ExpressionValue::Unary { operand, .. } => operand.locate(offset),
+        ExpressionValue::Group(expression) => expression.locate(offset),
+        ExpressionValue::Block {
+            statements,
+            return_value,
+        } => statements
+            .iter()
+            .find_map(|statement| find_in_statement(statement, offset))
+            .or_else(|| return_value.locate(offset)),
+        ExpressionValue::Conditional {
+            condition,
+            truthy,
+            falsy,
+        } => condition
+            .locate(offset)
+            .or_else(|| truthy.locate(offset))
+            .or_else(|| falsy.locate(offset)),
+        ExpressionValue::Call { callee, arguments } => callee
+            .locate(offset)
+            .or_else(|| arguments.iter().find_map(|a| a.locate(offset))),
+        ExpressionValue::Lambda(lambda) => lambda.body.locate(offset),
+        ExpressionValue::Interpolation(parts) => parts.iter().find_map(|p| p.locate(offset)),
+    }
+}
+
+fn find_in_statement<'a, 'ast, E: Locate<'ast>>(
+    statement: &'a Statement<'ast, E>,
+    offset: usize,
+) -> Option<Node<'a, 'ast>> {
+    let child = match &statement.value {
+        StatementValue::Block(statements) => statements
+            .iter()
+            .find_map(|statement| find_in_statement(statement, offset)),
+        StatementValue::Expression(expression)
+        | StatementValue::Return(expression)
+        | StatementValue::Assignment {
+            value: expression, ..
+        }
+        | StatementValue::Function {
+            body: expression, ..
+        } => expression.locate(offset),
+        StatementValue::Conditional {
+            condition,
+            truthy,
+            falsy,
+        } => condition
+            .locate(offset)
+            .or_else(|| find_in_statement(truthy, offset))
+            .or_else(|| {
+                falsy
+                    .as_ref()
+                    .and_then(|falsy| find_in_statement(falsy, offset))
+            }),
+        StatementValue::Struct { .. }
+        | StatementValue::Enum { .. }
+        | StatementValue::Trait { .. }
+        | StatementValue::TypeAlias { .. } => None,
+    };
+
+    child.or_else(|| {
+        statement
+            .span
+            .contains(offset)
+            .then(|| E::statement_node(statement))
+    })
+}
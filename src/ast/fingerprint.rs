@@ -0,0 +1,423 @@
+use super::{
+    Attribute, BinaryOperator, EnumMemberDeclaration, Expression, ExpressionValue, FunctionHeader,
+    Identifier, Lambda, Module, ParameterDeclaration, Primitive, Statement, StatementValue,
+    StructMemberDeclaration, Type, TypeValue, TypedExpression, UnaryOperator,
+};
+use std::hash::{Hash, Hasher};
+
+/// A hash of a tree that ignores source spans, the counterpart of
+/// [`StructurallyEq`](super::StructurallyEq): trees that are structurally equal have the same
+/// fingerprint, so reformatting the source does not change it. Fingerprints only depend on the
+/// tree, and are the same across runs and platforms.
+pub trait StructuralHash {
+    fn structural_hash(&self, state: &mut Fingerprint);
+
+    fn fingerprint(&self) -> u64 {
+        let mut state = Fingerprint::default();
+        self.structural_hash(&mut state);
+        state.finish()
+    }
+}
+
+/// A 64-bit FNV-1a hasher. Unlike the standard library's hasher its output is fixed, and numbers
+/// are hashed as little-endian 64-bit values whatever the platform.
+pub struct Fingerprint(u64);
+
+impl Default for Fingerprint {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fingerprint {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write_u64(i.into());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write_u64(i.into());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64);
+    }
+}
+
+impl<T: StructuralHash> StructuralHash for Box<T> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        (**self).structural_hash(state)
+    }
+}
+
+impl<T: StructuralHash> StructuralHash for Option<T> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        match self {
+            Some(value) => {
+                state.write_u8(1);
+                value.structural_hash(state);
+            }
+            None => state.write_u8(0),
+        }
+    }
+}
+
+impl<T: StructuralHash> StructuralHash for Vec<T> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        state.write_usize(self.len());
+        for value in self {
+            value.structural_hash(state);
+        }
+    }
+}
+
+impl StructuralHash for Expression<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.value.structural_hash(state)
+    }
+}
+
+impl StructuralHash for TypedExpression<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.ty.structural_hash(state);
+        self.value.structural_hash(state);
+    }
+}
+
+impl<Expression: StructuralHash> StructuralHash for ExpressionValue<'_, Expression> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        match self {
+            ExpressionValue::Primitive(primitive) => {
+                state.write_u8(0);
+                primitive.structural_hash(state);
+            }
+            ExpressionValue::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                state.write_u8(1);
+                operator.structural_hash(state);
+                left.structural_hash(state);
+                right.structural_hash(state);
+            }
+            ExpressionValue::Unary { operator, operand } => {
+                state.write_u8(2);
+                operator.structural_hash(state);
+                operand.structural_hash(state);
+            }
+            ExpressionValue::Group(expression) => {
+                state.write_u8(3);
+                expression.structural_hash(state);
+            }
+            ExpressionValue::Block {
+                statements,
+                return_value,
+            } => {
+                state.write_u8(4);
+                statements.structural_hash(state);
+                return_value.structural_hash(state);
+            }
+            ExpressionValue::Conditional {
+                condition,
+                truthy,
+                falsy,
+            } => {
+                state.write_u8(5);
+                condition.structural_hash(state);
+                truthy.structural_hash(state);
+                falsy.structural_hash(state);
+            }
+            ExpressionValue::Call { callee, arguments } => {
+                state.write_u8(6);
+                callee.structural_hash(state);
+                arguments.structural_hash(state);
+            }
+            ExpressionValue::Lambda(lambda) => {
+                state.write_u8(7);
+                lambda.structural_hash(state);
+            }
+            ExpressionValue::Path(path) => {
+                state.write_u8(8);
+                path.segments.structural_hash(state);
+            }
+            ExpressionValue::Interpolation(parts) => {
+                state.write_u8(9);
+                parts.structural_hash(state);
+            }
+        }
+    }
+}
+
+impl StructuralHash for Primitive<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        match self {
+            Primitive::Integer(value) => {
+                state.write_u8(0);
+                state.write_i64(*value);
+            }
+            Primitive::Int32(value) => {
+                state.write_u8(1);
+                state.write_i64(*value);
+            }
+            Primitive::Int64(value) => {
+                state.write_u8(2);
+                state.write_i64(*value);
+            }
+            Primitive::Decimal(value) => {
+                state.write_u8(3);
+                state.write_u64(value.to_bits());
+            }
+            Primitive::String(value) => {
+                state.write_u8(4);
+                value.hash(state);
+            }
+            Primitive::Identifier(value) => {
+                state.write_u8(5);
+                value.hash(state);
+            }
+            Primitive::Character(value) => {
+                state.write_u8(6);
+                state.write_u32(u32::from(*value));
+            }
+            Primitive::Boolean(value) => {
+                state.write_u8(7);
+                state.write_u8(u8::from(*value));
+            }
+            Primitive::Unit => state.write_u8(8),
+        }
+    }
+}
+
+impl StructuralHash for BinaryOperator {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        state.write_u8(match self {
+            BinaryOperator::Add => 0,
+            BinaryOperator::Subtract => 1,
+            BinaryOperator::Multiply => 2,
+            BinaryOperator::Divide => 3,
+            BinaryOperator::Modulo => 4,
+            BinaryOperator::Equality => 5,
+            BinaryOperator::Inequality => 6,
+            BinaryOperator::LessThan => 7,
+            BinaryOperator::LessThanOrEqual => 8,
+            BinaryOperator::GreaterThan => 9,
+            BinaryOperator::GreaterThanOrEqual => 10,
+            BinaryOperator::And => 11,
+            BinaryOperator::Or => 12,
+        })
+    }
+}
+
+impl StructuralHash for UnaryOperator {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        state.write_u8(match self {
+            UnaryOperator::Negate => 0,
+            UnaryOperator::Negative => 1,
+        })
+    }
+}
+
+impl StructuralHash for Lambda<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.parameters.structural_hash(state);
+        self.explicit_return_type.structural_hash(state);
+        self.body.structural_hash(state);
+    }
+}
+
+impl<Expression: StructuralHash> StructuralHash for Statement<'_, Expression> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.value.structural_hash(state)
+    }
+}
+
+impl<Expression: StructuralHash> StructuralHash for StatementValue<'_, Expression> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        match self {
+            StatementValue::Block(statements) => {
+                state.write_u8(0);
+                statements.structural_hash(state);
+            }
+            StatementValue::Expression(expression) => {
+                state.write_u8(1);
+                expression.structural_hash(state);
+            }
+            StatementValue::Assignment {
+                name,
+                explicit_type,
+                value,
+            } => {
+                state.write_u8(2);
+                name.hash(state);
+                explicit_type.structural_hash(state);
+                value.structural_hash(state);
+            }
+            StatementValue::Struct {
+                attributes,
+                name,
+                type_parameters,
+                fields,
+            } => {
+                state.write_u8(3);
+                attributes.structural_hash(state);
+                name.hash(state);
+                type_parameters.structural_hash(state);
+                fields.structural_hash(state);
+            }
+            StatementValue::Enum { name, variants } => {
+                state.write_u8(4);
+                name.hash(state);
+                variants.structural_hash(state);
+            }
+            StatementValue::Function { header, body } => {
+                state.write_u8(5);
+                header.structural_hash(state);
+                body.structural_hash(state);
+            }
+            StatementValue::Trait { name, functions } => {
+                state.write_u8(6);
+                name.hash(state);
+                functions.structural_hash(state);
+            }
+            StatementValue::Return(expression) => {
+                state.write_u8(7);
+                expression.structural_hash(state);
+            }
+            StatementValue::Conditional {
+                condition,
+                truthy,
+                falsy,
+            } => {
+                state.write_u8(8);
+                condition.structural_hash(state);
+                truthy.structural_hash(state);
+                falsy.structural_hash(state);
+            }
+            StatementValue::TypeAlias {
+                name,
+                explicit_type,
+            } => {
+                state.write_u8(9);
+                name.hash(state);
+                explicit_type.structural_hash(state);
+            }
+        }
+    }
+}
+
+impl StructuralHash for FunctionHeader<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.attributes.structural_hash(state);
+        self.name.hash(state);
+        self.type_parameters.structural_hash(state);
+        self.parameters.structural_hash(state);
+        self.explicit_return_type.structural_hash(state);
+    }
+}
+
+impl StructuralHash for Identifier<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.name.hash(state)
+    }
+}
+
+impl StructuralHash for Attribute<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.name.structural_hash(state);
+        self.arguments.structural_hash(state);
+    }
+}
+
+impl StructuralHash for ParameterDeclaration<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.name.hash(state);
+        self.explicit_type.structural_hash(state);
+    }
+}
+
+impl StructuralHash for StructMemberDeclaration<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.name.hash(state);
+        self.explicit_type.structural_hash(state);
+    }
+}
+
+impl StructuralHash for EnumMemberDeclaration<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.name.hash(state);
+        self.value_type.structural_hash(state);
+    }
+}
+
+impl<Expression: StructuralHash> StructuralHash for Module<'_, Expression> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        self.name.hash(state);
+        self.definitions.structural_hash(state);
+    }
+}
+
+// Types compare by their base type and ignore parameter names, and hash the same way
+impl StructuralHash for Type<'_> {
+    fn structural_hash(&self, state: &mut Fingerprint) {
+        match &self.base_type().value {
+            TypeValue::Unit => state.write_u8(0),
+            TypeValue::Boolean => state.write_u8(1),
+            TypeValue::Integer => state.write_u8(2),
+            TypeValue::Long => state.write_u8(3),
+            TypeValue::IntegerLiteral => state.write_u8(4),
+            TypeValue::Decimal => state.write_u8(5),
+            TypeValue::Character => state.write_u8(6),
+            TypeValue::String => state.write_u8(7),
+            // A base type is never an alias
+            TypeValue::Alias(..) => unreachable!(),
+            TypeValue::Symbol(name) => {
+                state.write_u8(8);
+                name.hash(state);
+            }
+            TypeValue::Generic(name) => {
+                state.write_u8(9);
+                name.hash(state);
+            }
+            TypeValue::Collection(element) => {
+                state.write_u8(10);
+                element.structural_hash(state);
+            }
+            TypeValue::Set(element) => {
+                state.write_u8(11);
+                element.structural_hash(state);
+            }
+            TypeValue::Function {
+                parameters,
+                return_type,
+                ..
+            } => {
+                state.write_u8(12);
+                parameters.structural_hash(state);
+                return_type.structural_hash(state);
+            }
+        }
+    }
+}
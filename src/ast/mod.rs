@@ -1,12 +1,19 @@
-use miette::SourceSpan;
+#[cfg(test)]
+mod tests;
 
 mod expression;
+mod find;
+mod fingerprint;
 mod module;
 mod span;
 mod statement;
+mod structure;
 mod typing;
 pub use self::expression::*;
+pub use self::find::*;
+pub use self::fingerprint::*;
 pub use self::module::*;
 pub use self::span::*;
 pub use self::statement::*;
+pub use self::structure::*;
 pub use self::typing::*;
@@ -7,28 +7,20 @@ pub trait Spannable<'ast>: Sized {
 
     fn at(span: miette::SourceSpan, value: Self::Value) -> Self;
 
+    /// Creates a node spanning from the start of the earliest span to the end of the latest
+    /// one. See [`CombineSpan::combine`] for how an empty list of spans is handled.
     fn at_multiple(spans: Vec<impl Into<miette::SourceSpan>>, value: Self::Value) -> Self {
         let spans = spans.into_iter().map(|s| s.into()).collect::<Vec<_>>();
 
-        let start = spans
-            .iter()
-            .min_by_key(|s| s.offset())
-            .map(|s| s.offset())
-            .unwrap_or(0);
-
-        let end = spans
-            .iter()
-            .max_by_key(|s| s.offset() + s.len())
-            .map(|s| s.offset() + s.len())
-            .unwrap_or(0);
-
-        let span = miette::SourceSpan::new(start.into(), end - start);
-
-        Self::at(span, value)
+        Self::at(SourceSpan::combine(spans), value)
     }
 }
 
 pub trait CombineSpan {
+    /// Combines spans into one covering all of them, gaps included.
+    ///
+    /// An empty list has nothing to cover and yields the empty span at offset 0. Callers that
+    /// can end up with no spans should supply their own fallback rather than rely on this.
     fn combine(spans: Vec<SourceSpan>) -> SourceSpan {
         let start = spans
             .iter()
@@ -48,6 +40,105 @@ pub trait CombineSpan {
 
 impl CombineSpan for SourceSpan {}
 
+/// How many columns a tab advances to the next tab stop by, as editors show it by default.
+pub const DEFAULT_TAB_WIDTH: usize = 8;
+
+/// A line and column in the source code, both starting at 1. Positions order by line, then
+/// by column. A `\r\n` line ending counts as a single newline, so CRLF and LF sources agree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// The position of the byte at `offset` in `source`. Columns count characters, not bytes.
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let (line, before) = Self::line_before(source, offset);
+
+        Self {
+            line,
+            column: before.chars().count() + 1,
+        }
+    }
+
+    /// Like [`Position::from_offset`], but a tab advances the column to the next multiple of
+    /// `tab_width`, so the column is the one an editor shows.
+    pub fn from_offset_with_tabs(source: &str, offset: usize, tab_width: usize) -> Self {
+        let (line, before) = Self::line_before(source, offset);
+        let tab_width = tab_width.max(1);
+
+        let column = before.chars().fold(0, |column, c| match c {
+            '\t' => (column / tab_width + 1) * tab_width,
+            _ => column + 1,
+        });
+
+        Self {
+            line,
+            column: column + 1,
+        }
+    }
+
+    /// The line `offset` lies on, and the part of that line before it. The `\r` of a `\r\n`
+    /// is left out, so the line ending sits at the same column as in an LF source.
+    fn line_before(source: &str, offset: usize) -> (usize, &str) {
+        let offset = offset.min(source.len());
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        let mut line = &before[line_start..];
+        if source[offset..].starts_with('\n') {
+            line = line.strip_suffix('\r').unwrap_or(line);
+        }
+
+        (before.matches('\n').count() + 1, line)
+    }
+
+    /// The byte offset of this position in `source`, or `None` if it lies outside of it.
+    pub fn to_offset(&self, source: &str) -> Option<usize> {
+        let line_start = match self.line {
+            0 => return None,
+            1 => 0,
+            line => source.match_indices('\n').nth(line - 2)?.0 + 1,
+        };
+
+        let line = source[line_start..].split('\n').next().unwrap_or_default();
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        match self.column {
+            0 => None,
+            column if column == line.chars().count() + 1 => Some(line_start + line.len()),
+            column => line
+                .char_indices()
+                .nth(column - 1)
+                .map(|(i, _)| line_start + i),
+        }
+    }
+}
+
+pub trait ContainsSpan {
+    /// Whether `offset` lies within the span. The end is exclusive, except for an empty span,
+    /// which contains its own offset.
+    fn contains(&self, offset: usize) -> bool;
+
+    /// Whether `position` lies within the span, resolved against `source`.
+    fn contains_position(&self, source: &str, position: &Position) -> bool {
+        position
+            .to_offset(source)
+            .is_some_and(|offset| self.contains(offset))
+    }
+}
+
+impl ContainsSpan for SourceSpan {
+    fn contains(&self, offset: usize) -> bool {
+        if self.is_empty() {
+            return offset == self.offset();
+        }
+
+        (self.offset()..self.offset() + self.len()).contains(&offset)
+    }
+}
+
 impl<'ast> Spannable<'ast> for Expression<'ast> {
     type Value = ExpressionValue<'ast, Expression<'ast>>;
 
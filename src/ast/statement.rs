@@ -1,4 +1,4 @@
-use super::{Type, TypedExpression};
+use super::{Identifier, Primitive, Type, TypedExpression};
 use std::{
     borrow::Cow,
     fmt::{Display, Formatter},
@@ -18,10 +18,13 @@ pub enum StatementValue<'ast, Expression> {
     Expression(Expression),
     Assignment {
         name: Cow<'ast, str>,
+        explicit_type: Option<Type<'ast>>,
         value: Expression,
     },
     Struct {
+        attributes: Vec<Attribute<'ast>>,
         name: Cow<'ast, str>,
+        type_parameters: Vec<Identifier<'ast>>,
         fields: Vec<StructMemberDeclaration<'ast>>,
     },
     Enum {
@@ -53,10 +56,10 @@ impl<Expression: Display> Display for StatementValue<'_, Expression> {
         match self {
             StatementValue::Block(vec) => write!(f, "a block of {} statements", vec.len()),
             StatementValue::Expression(expression) => write!(f, "{}", expression),
-            StatementValue::Assignment { name, value } => {
+            StatementValue::Assignment { name, value, .. } => {
                 write!(f, "`{}` assignment with {}", name, value)
             }
-            StatementValue::Struct { name, fields: _ } => write!(f, "`{}` struct", name),
+            StatementValue::Struct { name, .. } => write!(f, "`{}` struct", name),
             StatementValue::Enum { name, variants: _ } => write!(f, "`{}` enum", name),
             StatementValue::Function { header, body: _ } => write!(f, "`{}` function", header.name),
             StatementValue::Trait { name, functions: _ } => write!(f, "`{}` trait", name),
@@ -90,12 +93,20 @@ impl<Expression: Display> Statement<'_, Expression> {
 
 #[derive(Debug, Clone)]
 pub struct FunctionHeader<'ast> {
+    pub attributes: Vec<Attribute<'ast>>,
     pub name: Cow<'ast, str>,
+    pub type_parameters: Vec<Identifier<'ast>>,
     pub parameters: Vec<ParameterDeclaration<'ast>>,
     pub explicit_return_type: Option<Type<'ast>>,
     pub span: miette::SourceSpan,
 }
 
+impl<'ast> FunctionHeader<'ast> {
+    pub fn parameter_names(&self) -> Vec<Cow<'ast, str>> {
+        self.parameters.iter().map(|p| p.name.clone()).collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ParameterDeclaration<'ast> {
     pub name: Cow<'ast, str>,
@@ -116,3 +127,11 @@ pub struct EnumMemberDeclaration<'ast> {
     pub value_type: Option<Type<'ast>>,
     pub span: miette::SourceSpan,
 }
+
+/// An attribute placed before a declaration, such as `#[inline]` or `#[deprecated("use bar")]`.
+#[derive(Debug, Clone)]
+pub struct Attribute<'ast> {
+    pub name: Identifier<'ast>,
+    pub arguments: Vec<Primitive<'ast>>,
+    pub span: miette::SourceSpan,
+}
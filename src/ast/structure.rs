@@ -0,0 +1,289 @@
+use super::{
+    Attribute, EnumMemberDeclaration, Expression, ExpressionValue, FunctionHeader, Identifier,
+    Lambda, Module, ParameterDeclaration, Statement, StatementValue, StructMemberDeclaration,
+    TypedExpression,
+};
+
+/// Structural equality that ignores source spans, so two trees parsed from differently
+/// formatted source compare equal when they have the same shape.
+pub trait StructurallyEq {
+    fn structurally_eq(&self, other: &Self) -> bool;
+}
+
+impl<T: StructurallyEq> StructurallyEq for Box<T> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        (**self).structurally_eq(other)
+    }
+}
+
+impl<T: StructurallyEq> StructurallyEq for Option<T> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.structurally_eq(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: StructurallyEq> StructurallyEq for Vec<T> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.structurally_eq(b))
+    }
+}
+
+impl StructurallyEq for Expression<'_> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.value.structurally_eq(&other.value)
+    }
+}
+
+impl StructurallyEq for TypedExpression<'_> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.ty == other.ty && self.value.structurally_eq(&other.value)
+    }
+}
+
+impl<Expression: StructurallyEq> StructurallyEq for ExpressionValue<'_, Expression> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ExpressionValue::Primitive(a), ExpressionValue::Primitive(b)) => a == b,
+            (
+                ExpressionValue::Binary {
+                    operator: a_operator,
+                    left: a_left,
+                    right: a_right,
+                },
+                ExpressionValue::Binary {
+                    operator: b_operator,
+                    left: b_left,
+                    right: b_right,
+                },
+            ) => {
+                a_operator == b_operator
+                    && a_left.structurally_eq(b_left)
+                    && a_right.structurally_eq(b_right)
+            }
+            (
+                ExpressionValue::Unary {
+                    operator: a_operator,
+                    operand: a_operand,
+                },
+                ExpressionValue::Unary {
+                    operator: b_operator,
+                    operand: b_operand,
+                },
+            ) => a_operator == b_operator && a_operand.structurally_eq(b_operand),
+            (ExpressionValue::Group(a), ExpressionValue::Group(b)) => a.structurally_eq(b),
+            (
+                ExpressionValue::Block {
+                    statements: a_statements,
+                    return_value: a_return_value,
+                },
+                ExpressionValue::Block {
+                    statements: b_statements,
+                    return_value: b_return_value,
+                },
+            ) => {
+                a_statements.structurally_eq(b_statements)
+                    && a_return_value.structurally_eq(b_return_value)
+            }
+            (
+                ExpressionValue::Conditional {
+                    condition: a_condition,
+                    truthy: a_truthy,
+                    falsy: a_falsy,
+                },
+                ExpressionValue::Conditional {
+                    condition: b_condition,
+                    truthy: b_truthy,
+                    falsy: b_falsy,
+                },
+            ) => {
+                a_condition.structurally_eq(b_condition)
+                    && a_truthy.structurally_eq(b_truthy)
+                    && a_falsy.structurally_eq(b_falsy)
+            }
+            (
+                ExpressionValue::Call {
+                    callee: a_callee,
+                    arguments: a_arguments,
+                },
+                ExpressionValue::Call {
+                    callee: b_callee,
+                    arguments: b_arguments,
+                },
+            ) => a_callee.structurally_eq(b_callee) && a_arguments.structurally_eq(b_arguments),
+            (ExpressionValue::Lambda(a), ExpressionValue::Lambda(b)) => a.structurally_eq(b),
+            (ExpressionValue::Interpolation(a), ExpressionValue::Interpolation(b)) => {
+                a.structurally_eq(b)
+            }
+            (ExpressionValue::Path(a), ExpressionValue::Path(b)) => {
+                a.segments.structurally_eq(&b.segments)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl StructurallyEq for Lambda<'_> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.parameters.structurally_eq(&other.parameters)
+            && self.explicit_return_type == other.explicit_return_type
+            && self.body.structurally_eq(&other.body)
+    }
+}
+
+impl<Expression: StructurallyEq> StructurallyEq for Statement<'_, Expression> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.value.structurally_eq(&other.value)
+    }
+}
+
+impl<Expression: StructurallyEq> StructurallyEq for StatementValue<'_, Expression> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (StatementValue::Block(a), StatementValue::Block(b)) => a.structurally_eq(b),
+            (StatementValue::Expression(a), StatementValue::Expression(b)) => a.structurally_eq(b),
+            (
+                StatementValue::Assignment {
+                    name: a_name,
+                    explicit_type: a_explicit_type,
+                    value: a_value,
+                },
+                StatementValue::Assignment {
+                    name: b_name,
+                    explicit_type: b_explicit_type,
+                    value: b_value,
+                },
+            ) => {
+                a_name == b_name
+                    && a_explicit_type == b_explicit_type
+                    && a_value.structurally_eq(b_value)
+            }
+            (
+                StatementValue::Struct {
+                    attributes: a_attributes,
+                    name: a_name,
+                    type_parameters: a_type_parameters,
+                    fields: a_fields,
+                },
+                StatementValue::Struct {
+                    attributes: b_attributes,
+                    name: b_name,
+                    type_parameters: b_type_parameters,
+                    fields: b_fields,
+                },
+            ) => {
+                a_attributes.structurally_eq(b_attributes)
+                    && a_name == b_name
+                    && a_type_parameters.structurally_eq(b_type_parameters)
+                    && a_fields.structurally_eq(b_fields)
+            }
+            (
+                StatementValue::Enum {
+                    name: a_name,
+                    variants: a_variants,
+                },
+                StatementValue::Enum {
+                    name: b_name,
+                    variants: b_variants,
+                },
+            ) => a_name == b_name && a_variants.structurally_eq(b_variants),
+            (
+                StatementValue::Function {
+                    header: a_header,
+                    body: a_body,
+                },
+                StatementValue::Function {
+                    header: b_header,
+                    body: b_body,
+                },
+            ) => a_header.structurally_eq(b_header) && a_body.structurally_eq(b_body),
+            (
+                StatementValue::Trait {
+                    name: a_name,
+                    functions: a_functions,
+                },
+                StatementValue::Trait {
+                    name: b_name,
+                    functions: b_functions,
+                },
+            ) => a_name == b_name && a_functions.structurally_eq(b_functions),
+            (StatementValue::Return(a), StatementValue::Return(b)) => a.structurally_eq(b),
+            (
+                StatementValue::Conditional {
+                    condition: a_condition,
+                    truthy: a_truthy,
+                    falsy: a_falsy,
+                },
+                StatementValue::Conditional {
+                    condition: b_condition,
+                    truthy: b_truthy,
+                    falsy: b_falsy,
+                },
+            ) => {
+                a_condition.structurally_eq(b_condition)
+                    && a_truthy.structurally_eq(b_truthy)
+                    && a_falsy.structurally_eq(b_falsy)
+            }
+            (
+                StatementValue::TypeAlias {
+                    name: a_name,
+                    explicit_type: a_explicit_type,
+                },
+                StatementValue::TypeAlias {
+                    name: b_name,
+                    explicit_type: b_explicit_type,
+                },
+            ) => a_name == b_name && a_explicit_type == b_explicit_type,
+            _ => false,
+        }
+    }
+}
+
+impl StructurallyEq for FunctionHeader<'_> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.attributes.structurally_eq(&other.attributes)
+            && self.name == other.name
+            && self.type_parameters.structurally_eq(&other.type_parameters)
+            && self.parameters.structurally_eq(&other.parameters)
+            && self.explicit_return_type == other.explicit_return_type
+    }
+}
+
+impl StructurallyEq for Identifier<'_> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl StructurallyEq for Attribute<'_> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.name.structurally_eq(&other.name) && self.arguments == other.arguments
+    }
+}
+
+impl StructurallyEq for ParameterDeclaration<'_> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.explicit_type == other.explicit_type
+    }
+}
+
+impl StructurallyEq for StructMemberDeclaration<'_> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.explicit_type == other.explicit_type
+    }
+}
+
+impl StructurallyEq for EnumMemberDeclaration<'_> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value_type == other.value_type
+    }
+}
+
+impl<Expression: StructurallyEq> StructurallyEq for Module<'_, Expression> {
+    fn structurally_eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.definitions.structurally_eq(&other.definitions)
+    }
+}
@@ -0,0 +1,175 @@
+use super::*;
+use miette::SourceSpan;
+use std::hash::{Hash, Hasher};
+
+#[test]
+fn structural_equality_ignores_spans() {
+    let a = typed_addition(SourceSpan::new(0.into(), 5), 0);
+    let b = typed_addition(SourceSpan::new(10.into(), 9), 12);
+
+    assert!(a.structurally_eq(&b));
+}
+
+#[test]
+fn structural_equality_compares_values() {
+    let a = typed_addition(SourceSpan::new(0.into(), 5), 0);
+    let mut b = a.clone();
+    b.value = ExpressionValue::Primitive(Primitive::Integer(3));
+
+    assert!(!a.structurally_eq(&b));
+}
+
+#[test]
+fn combine_single_span() {
+    let span = SourceSpan::new(4.into(), 3);
+
+    assert_eq!(SourceSpan::combine(vec![span]), span);
+}
+
+#[test]
+fn combine_multiple_spans() {
+    let spans = vec![
+        SourceSpan::new(10.into(), 2),
+        SourceSpan::new(4.into(), 3),
+        SourceSpan::new(7.into(), 1),
+    ];
+
+    assert_eq!(SourceSpan::combine(spans), SourceSpan::new(4.into(), 8));
+}
+
+#[test]
+fn combine_no_spans() {
+    assert_eq!(SourceSpan::combine(vec![]), SourceSpan::new(0.into(), 0));
+
+    let expression = Expression::at_multiple(
+        Vec::<SourceSpan>::new(),
+        ExpressionValue::Primitive(Primitive::Unit),
+    );
+    assert_eq!(expression.span, SourceSpan::new(0.into(), 0));
+}
+
+#[test]
+fn position_ordering() {
+    let a = Position { line: 1, column: 8 };
+    let b = Position { line: 2, column: 1 };
+    let c = Position { line: 2, column: 3 };
+
+    assert!(a < b);
+    assert!(b < c);
+    assert_eq!(a.max(c), c);
+}
+
+#[test]
+fn position_from_offset() {
+    let source = "fn main() {\n  let a = 1;\n}";
+
+    assert_eq!(
+        Position::from_offset(source, 0),
+        Position { line: 1, column: 1 }
+    );
+    assert_eq!(
+        Position::from_offset(source, 14),
+        Position { line: 2, column: 3 }
+    );
+    assert_eq!(Position { line: 2, column: 3 }.to_offset(source), Some(14));
+    assert_eq!(Position { line: 4, column: 1 }.to_offset(source), None);
+}
+
+#[test]
+fn position_from_offset_with_crlf() {
+    let lf = "let a = 1;\nlet b = 2;";
+    let crlf = "let a = 1;\r\nlet b = 2;";
+
+    assert_eq!(
+        Position::from_offset(crlf, 11),
+        Position::from_offset(lf, 10)
+    );
+    assert_eq!(
+        Position::from_offset(crlf, 12),
+        Position { line: 2, column: 1 }
+    );
+    assert_eq!(Position::from_offset(crlf, 11).to_offset(crlf), Some(10));
+}
+
+#[test]
+fn span_contains_boundaries() {
+    let span = SourceSpan::new(4.into(), 3);
+
+    assert!(!span.contains(3));
+    assert!(span.contains(4));
+    assert!(span.contains(6));
+    assert!(!span.contains(7));
+
+    let empty = SourceSpan::new(4.into(), 0);
+    assert!(empty.contains(4));
+    assert!(!empty.contains(5));
+}
+
+#[test]
+fn span_contains_position() {
+    let source = "fn main() {\n  let a = 1;\n}";
+    let span = SourceSpan::new(14.into(), 3);
+
+    assert!(span.contains_position(source, &Position { line: 2, column: 3 }));
+    assert!(span.contains_position(source, &Position { line: 2, column: 5 }));
+    assert!(!span.contains_position(source, &Position { line: 2, column: 6 }));
+    assert!(!span.contains_position(source, &Position { line: 1, column: 3 }));
+}
+
+#[test]
+fn verbose_function_type() {
+    let span = SourceSpan::new(0.into(), 0);
+    let ty = Type::function(
+        span,
+        vec![Type::integer(span), Type::integer(span)],
+        Type::long(span),
+    );
+
+    assert_eq!(ty.verbose(), "fn(i32, i32) -> i64");
+
+    let ty = ty.parameter_names(vec!["x".into(), "y".into()]);
+    assert_eq!(ty.verbose(), "fn(x ~ i32, y ~ i32) -> i64");
+}
+
+fn typed_addition(span: SourceSpan, offset: usize) -> TypedExpression<'static> {
+    let left = Expression::at(
+        SourceSpan::new(offset.into(), 1),
+        ExpressionValue::Primitive(Primitive::Integer(1)),
+    );
+    let right = Expression::at(
+        SourceSpan::new((offset + 4).into(), 1),
+        ExpressionValue::Primitive(Primitive::Integer(2)),
+    );
+
+    Expression::at(
+        span,
+        ExpressionValue::Binary {
+            operator: BinaryOperator::Add,
+            left: Box::new(left),
+            right: Box::new(right),
+        },
+    )
+    .to_typed(Type::integer(span))
+}
+
+#[test]
+fn fingerprint_ignores_formatting() {
+    let source = "fn main() { let a ~ i32 = 1 + 2; a }";
+    let reformatted = "fn main() {\n    let a ~ i32 = 1 + 2;\n    a\n}\n";
+    let changed = "fn main() { let a ~ i32 = 1 + 3; a }";
+
+    let fingerprint = |source| {
+        let (modules, diagnostics) = crate::check(source);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        modules.unwrap().fingerprint()
+    };
+    let content_hash = |source: &str| {
+        let mut state = Fingerprint::default();
+        source.hash(&mut state);
+        state.finish()
+    };
+
+    assert_eq!(fingerprint(source), fingerprint(reformatted));
+    assert_ne!(content_hash(source), content_hash(reformatted));
+    assert_ne!(fingerprint(source), fingerprint(changed));
+}
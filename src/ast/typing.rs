@@ -47,6 +47,22 @@ impl<'ast> Type<'ast> {
         }
     }
 
+    pub fn long(span: SourceSpan) -> Self {
+        Self {
+            value: TypeValue::Long,
+            span,
+            original_span: None,
+        }
+    }
+
+    pub fn integer_literal(span: SourceSpan) -> Self {
+        Self {
+            value: TypeValue::IntegerLiteral,
+            span,
+            original_span: None,
+        }
+    }
+
     pub fn decimal(span: SourceSpan) -> Self {
         Self {
             value: TypeValue::Decimal,
@@ -79,6 +95,14 @@ impl<'ast> Type<'ast> {
         }
     }
 
+    pub fn generic(span: SourceSpan, name: Cow<'ast, str>) -> Self {
+        Self {
+            value: TypeValue::Generic(name),
+            span,
+            original_span: None,
+        }
+    }
+
     pub fn collection(span: SourceSpan, element: Type<'ast>) -> Self {
         Self {
             value: TypeValue::Collection(Box::new(element)),
@@ -103,6 +127,7 @@ impl<'ast> Type<'ast> {
         Self {
             value: TypeValue::Function {
                 parameters,
+                parameter_names: None,
                 return_type: Box::new(return_type),
             },
             span,
@@ -126,6 +151,22 @@ impl<'ast> Type<'ast> {
         self
     }
 
+    /// Names the parameters of a function type, so it can be rendered with [`Type::verbose`].
+    pub fn parameter_names(mut self, names: Vec<Cow<'ast, str>>) -> Self {
+        if let TypeValue::Function {
+            parameter_names, ..
+        } = &mut self.value
+        {
+            *parameter_names = Some(names);
+        }
+        self
+    }
+
+    /// Renders the type the way it is written in source code, see [`TypeValue::verbose`].
+    pub fn verbose(&self) -> String {
+        self.value.verbose()
+    }
+
     pub fn base_type(&self) -> &Type<'ast> {
         if let TypeValue::Alias(_, alias) = &self.value {
             return alias.base_type();
@@ -133,6 +174,73 @@ impl<'ast> Type<'ast> {
 
         self
     }
+
+    /// Whether this type is an integer literal that has not been given a width yet.
+    pub fn is_integer_literal(&self) -> bool {
+        self.base_type().value == TypeValue::IntegerLiteral
+    }
+
+    /// Whether this type is an integer of any width, including an untyped literal.
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self.base_type().value,
+            TypeValue::Integer | TypeValue::Long | TypeValue::IntegerLiteral
+        )
+    }
+
+    /// Whether a value of this type can be used where `other` is expected. Integer literals
+    /// unify with an integer of any width, and function types unify when they take as many
+    /// parameters and their parameters and return types unify.
+    pub fn unifies_with(&self, other: &Type<'ast>) -> bool {
+        if self.is_integer() && other.is_integer() {
+            return self.is_integer_literal() || other.is_integer_literal() || self == other;
+        }
+
+        if let (
+            TypeValue::Function {
+                parameters,
+                return_type,
+                ..
+            },
+            TypeValue::Function {
+                parameters: other_parameters,
+                return_type: other_return_type,
+                ..
+            },
+        ) = (&self.base_type().value, &other.base_type().value)
+        {
+            // Parameters are checked the other way around, since the arguments of `other` are
+            // passed to this function
+            return parameters.len() == other_parameters.len()
+                && parameters
+                    .iter()
+                    .zip(other_parameters)
+                    .all(|(parameter, other)| other.unifies_with(parameter))
+                && return_type.unifies_with(other_return_type);
+        }
+
+        self == other
+    }
+
+    /// Gives an untyped integer literal its concrete width from `context`. Any other type is
+    /// returned as is.
+    pub fn concretize(self, context: &Type<'ast>) -> Type<'ast> {
+        if self.is_integer_literal() && context.is_integer() {
+            let span = self.span;
+            return context.clone().span(span);
+        }
+
+        self
+    }
+
+    /// Gives an untyped integer literal its default width, `i32`.
+    pub fn concretize_default(self) -> Type<'ast> {
+        if self.is_integer_literal() {
+            return Type::integer(self.span);
+        }
+
+        self
+    }
 }
 
 impl Eq for Type<'_> {}
@@ -145,62 +253,152 @@ impl PartialEq for Type<'_> {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub enum TypeValue<'ast> {
     Unit,
     Boolean,
     Integer,
+    Long,
+    IntegerLiteral,
     Decimal,
     Character,
     String,
     Alias(Cow<'ast, str>, Box<Type<'ast>>),
     Symbol(Cow<'ast, str>),
+    /// A type parameter of a generic function, such as the `T` in `fn id<T>(x ~ T) -> T`.
+    Generic(Cow<'ast, str>),
     Collection(Box<Type<'ast>>),
     Set(Box<Type<'ast>>),
     Function {
         parameters: Vec<Type<'ast>>,
+        parameter_names: Option<Vec<Cow<'ast, str>>>,
         return_type: Box<Type<'ast>>,
     },
 }
 
+impl Eq for TypeValue<'_> {}
+impl PartialEq for TypeValue<'_> {
+    // Parameter names are documentation, two function types with the same signature are equal
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TypeValue::Alias(a, a_alias), TypeValue::Alias(b, b_alias)) => {
+                a == b && a_alias == b_alias
+            }
+            (TypeValue::Symbol(a), TypeValue::Symbol(b)) => a == b,
+            (TypeValue::Generic(a), TypeValue::Generic(b)) => a == b,
+            (TypeValue::Collection(a), TypeValue::Collection(b)) => a == b,
+            (TypeValue::Set(a), TypeValue::Set(b)) => a == b,
+            (
+                TypeValue::Function {
+                    parameters: a_parameters,
+                    return_type: a_return_type,
+                    ..
+                },
+                TypeValue::Function {
+                    parameters: b_parameters,
+                    return_type: b_return_type,
+                    ..
+                },
+            ) => a_parameters == b_parameters && a_return_type == b_return_type,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
 impl Display for Type<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.value)
     }
 }
 
+impl TypeValue<'_> {
+    /// Renders the type the way it is written in source code. Function types include their
+    /// parameter names when they are known, such as `fn(x ~ i32, y ~ i32) -> i64`.
+    pub fn verbose(&self) -> String {
+        match self {
+            TypeValue::Unit => "()".to_string(),
+            TypeValue::Boolean => "bool".to_string(),
+            TypeValue::Integer => "i32".to_string(),
+            TypeValue::Long => "i64".to_string(),
+            TypeValue::IntegerLiteral => "{integer}".to_string(),
+            TypeValue::Decimal => "dec".to_string(),
+            TypeValue::Character => "char".to_string(),
+            TypeValue::String => "str".to_string(),
+            TypeValue::Alias(name, _) | TypeValue::Symbol(name) | TypeValue::Generic(name) => {
+                name.to_string()
+            }
+            TypeValue::Collection(element) => format!("[{}]", element.verbose()),
+            TypeValue::Set(element) => format!("{{{}}}", element.verbose()),
+            TypeValue::Function {
+                parameters,
+                parameter_names,
+                return_type,
+            } => {
+                let parameters = parameters
+                    .iter()
+                    .enumerate()
+                    .map(
+                        |(i, p)| match parameter_names.as_ref().and_then(|n| n.get(i)) {
+                            Some(name) => format!("{} ~ {}", name, p.verbose()),
+                            None => p.verbose(),
+                        },
+                    )
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                match return_type.value {
+                    TypeValue::Unit => format!("fn({})", parameters),
+                    _ => format!("fn({}) -> {}", parameters, return_type.verbose()),
+                }
+            }
+        }
+    }
+
+    /// The width of a sized integer type.
+    pub fn bits(&self) -> Option<u32> {
+        match self {
+            TypeValue::Integer => Some(32),
+            TypeValue::Long => Some(64),
+            _ => None,
+        }
+    }
+
+    /// Describes many values of the type, such as the elements of a list.
+    fn plural(&self) -> String {
+        match self {
+            TypeValue::Unit => "empty values".to_string(),
+            TypeValue::Boolean => "booleans".to_string(),
+            TypeValue::Integer => "32-bit integers".to_string(),
+            TypeValue::Long => "64-bit integers".to_string(),
+            TypeValue::IntegerLiteral => "integers".to_string(),
+            TypeValue::Decimal => "decimals".to_string(),
+            TypeValue::Character => "characters".to_string(),
+            TypeValue::String => "strings".to_string(),
+            TypeValue::Alias(name, _) | TypeValue::Symbol(name) | TypeValue::Generic(name) => {
+                format!("`{}` values", name)
+            }
+            TypeValue::Collection(element) => format!("lists of {}", element.value.plural()),
+            TypeValue::Set(element) => format!("sets of {}", element.value.plural()),
+            TypeValue::Function { .. } => format!("functions of type `{}`", self.verbose()),
+        }
+    }
+}
+
 impl Display for TypeValue<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self {
             TypeValue::Unit => write!(f, "nothing"),
             TypeValue::Boolean => write!(f, "a boolean"),
-            TypeValue::Integer => write!(f, "an integer"),
+            TypeValue::Integer => write!(f, "a 32-bit integer"),
+            TypeValue::Long => write!(f, "a 64-bit integer"),
+            TypeValue::IntegerLiteral => write!(f, "an integer"),
             TypeValue::Decimal => write!(f, "a decimal"),
             TypeValue::Character => write!(f, "a character"),
             TypeValue::String => write!(f, "a string"),
-            TypeValue::Symbol(name) => write!(f, "`{}`", name),
-            TypeValue::Collection(element) => write!(f, "[{}]", element),
-            TypeValue::Set(element) => write!(f, "{{{}}}", element),
-            TypeValue::Function {
-                parameters,
-                return_type,
-            } => {
-                write!(
-                    f,
-                    "fn ({})",
-                    parameters
-                        .iter()
-                        .map(|p| p.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", "),
-                )?;
-
-                if return_type.value != TypeValue::Unit {
-                    write!(f, " -> {}", return_type)?;
-                }
-
-                Ok(())
-            }
+            TypeValue::Symbol(name) | TypeValue::Generic(name) => write!(f, "`{}`", name),
+            TypeValue::Collection(element) => write!(f, "a list of {}", element.value.plural()),
+            TypeValue::Set(element) => write!(f, "a set of {}", element.value.plural()),
+            TypeValue::Function { .. } => write!(f, "a function `{}`", self.verbose()),
             TypeValue::Alias(name, alias) => write!(f, "`{}` type alias with type {}", name, alias),
         }
     }
@@ -2,6 +2,9 @@ use owo_colors::{Style, Styled};
 
 use crate::lexer::{Lexer, TokenKind};
 
+#[cfg(test)]
+mod tests;
+
 pub struct SomHighlighter {}
 struct SomHighlighterState {}
 
@@ -16,63 +19,84 @@ impl miette::highlighters::Highlighter for SomHighlighter {
 
 impl miette::highlighters::HighlighterState for SomHighlighterState {
     fn highlight_line<'s>(&mut self, line: &'s str) -> Vec<Styled<&'s str>> {
+        let comment = Style::new().fg_rgb::<92, 99, 112>().italic();
         let mut sections: Vec<Styled<&'s str>> = vec![];
+        let mut end = 0;
+
+        // Plain comments are skipped by the lexer, so any text between tokens that is not
+        // whitespace is a comment
+        let gap = |text: &'s str| match text.trim().is_empty() {
+            true => Style::new().remove_all_effects().style(text),
+            false => comment.style(text),
+        };
 
-        for word in line.split(' ') {
-            for token in Lexer::<'s>::new(word) {
-                let style: Style = match &token {
-                    Ok(token) => match &token.kind {
-                        // Comment / quote -> 92, 99, 112 + italic
-                        TokenKind::If
-                        | TokenKind::Else
-                        | TokenKind::Let
-                        | TokenKind::Type
-                        | TokenKind::Struct
-                        | TokenKind::Enum
-                        | TokenKind::Function
-                        | TokenKind::Trait
-                        | TokenKind::Return => Style::new().fg_rgb::<197, 120, 221>(),
-                        TokenKind::Identifier => Style::new().fg_rgb::<224, 108, 117>(),
-                        TokenKind::String | TokenKind::Character => {
-                            Style::new().fg_rgb::<152, 195, 121>().italic()
-                        }
-                        TokenKind::Integer | TokenKind::Decimal => {
-                            Style::new().fg_rgb::<209, 154, 102>()
-                        }
-                        TokenKind::Boolean => Style::new().fg_rgb::<86, 156, 214>(),
-                        TokenKind::IntegerType
-                        | TokenKind::DecimalType
-                        | TokenKind::BooleanType
-                        | TokenKind::StringType
-                        | TokenKind::CharacterType => {
-                            Style::new().fg_rgb::<86, 156, 214>().italic()
-                        }
-                        TokenKind::Equal
-                        | TokenKind::LessThan
-                        | TokenKind::GreaterThan
-                        | TokenKind::LessThanOrEqual
-                        | TokenKind::GreaterThanOrEqual
-                        | TokenKind::Equality
-                        | TokenKind::Inequality
-                        | TokenKind::Percent
-                        | TokenKind::Not
-                        | TokenKind::And
-                        | TokenKind::Pipe
-                        | TokenKind::Caret
-                        | TokenKind::Or
-                        | TokenKind::Semicolon
-                        | TokenKind::Comma => Style::new().fg_rgb::<200, 200, 200>(),
-                        _ => Style::new().fg_rgb::<171, 178, 191>(),
-                    },
-                    Err(_) => return vec![Style::new().remove_all_effects().white().style(line)],
-                };
+        for token in Lexer::<'s>::new(line) {
+            let token = match token {
+                Ok(token) => token,
+                Err(_) => return vec![Style::new().remove_all_effects().white().style(line)],
+            };
 
-                let token = token.unwrap();
-                sections.push(style.style(token.original));
+            let start = token.span.offset();
+            if start > end {
+                sections.push(gap(&line[end..start]));
             }
-            sections.push(Style::new().remove_all_effects().style(" "));
+            end = start + token.span.len();
+
+            let style = match token.kind {
+                TokenKind::DocComment => comment,
+                kind => style(&kind),
+            };
+            sections.push(style.style(token.original));
+        }
+
+        if end < line.len() {
+            sections.push(gap(&line[end..]));
         }
 
         sections
     }
 }
+
+fn style(kind: &TokenKind) -> Style {
+    match kind {
+        TokenKind::If
+        | TokenKind::Else
+        | TokenKind::While
+        | TokenKind::For
+        | TokenKind::Let
+        | TokenKind::Type
+        | TokenKind::Struct
+        | TokenKind::Enum
+        | TokenKind::Function
+        | TokenKind::Trait
+        | TokenKind::Return => Style::new().fg_rgb::<197, 120, 221>(),
+        TokenKind::Identifier => Style::new().fg_rgb::<224, 108, 117>(),
+        TokenKind::String | TokenKind::InterpolatedString | TokenKind::Character => {
+            Style::new().fg_rgb::<152, 195, 121>().italic()
+        }
+        TokenKind::Integer | TokenKind::Decimal => Style::new().fg_rgb::<209, 154, 102>(),
+        TokenKind::Boolean => Style::new().fg_rgb::<86, 156, 214>(),
+        TokenKind::IntegerType
+        | TokenKind::LongType
+        | TokenKind::DecimalType
+        | TokenKind::BooleanType
+        | TokenKind::StringType
+        | TokenKind::CharacterType => Style::new().fg_rgb::<86, 156, 214>().italic(),
+        TokenKind::Equal
+        | TokenKind::LessThan
+        | TokenKind::GreaterThan
+        | TokenKind::LessThanOrEqual
+        | TokenKind::GreaterThanOrEqual
+        | TokenKind::Equality
+        | TokenKind::Inequality
+        | TokenKind::Percent
+        | TokenKind::Not
+        | TokenKind::And
+        | TokenKind::Pipe
+        | TokenKind::Caret
+        | TokenKind::Or
+        | TokenKind::Semicolon
+        | TokenKind::Comma => Style::new().fg_rgb::<200, 200, 200>(),
+        _ => Style::new().fg_rgb::<171, 178, 191>(),
+    }
+}
@@ -0,0 +1,27 @@
+use super::*;
+use miette::highlighters::HighlighterState;
+use pretty_assertions::assert_eq;
+
+fn sections(line: &str) -> Vec<String> {
+    SomHighlighterState {}
+        .highlight_line(line)
+        .iter()
+        .map(|section| section.inner().to_string())
+        .collect()
+}
+
+#[test]
+fn comments_after_code() {
+    assert_eq!(
+        sections("let a = 1; // one"),
+        vec!["let", " ", "a", " ", "=", " ", "1", ";", " // one"]
+    );
+}
+
+#[test]
+fn comment_markers_in_strings() {
+    assert_eq!(
+        sections(r#"let url = "http://x";"#),
+        vec!["let", " ", "url", " ", "=", " ", r#""http://x""#, ";"]
+    );
+}
@@ -6,11 +6,25 @@ pub use token::*;
 
 use miette::{LabeledSpan, Result, SourceSpan};
 
+/// The suffixes a number can have, such as the `l` in `5l`.
+const NUMBER_SUFFIXES: [&str; 11] = [
+    "i", "l", "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f64",
+];
+
 pub struct Lexer<'ast> {
     whole: &'ast str,
     remainder: &'ast str,
     byte_offset: usize,
-    peeked: Option<Result<Token<'ast>, miette::Error>>,
+    peeked: Option<Peeked<'ast>>,
+    skip_doc_comments: bool,
+}
+
+/// A token that has been lexed ahead of time, along with the position the lexer will be at once
+/// the token is consumed. Until then the lexer stays at its position before the token.
+struct Peeked<'ast> {
+    token: Option<Result<Token<'ast>, miette::Error>>,
+    remainder: &'ast str,
+    byte_offset: usize,
 }
 
 impl<'ast> Lexer<'ast> {
@@ -20,9 +34,17 @@ impl<'ast> Lexer<'ast> {
             remainder: input,
             byte_offset: 0,
             peeked: None,
+            skip_doc_comments: false,
         }
     }
 
+    /// Skips doc comments as if they were plain comments, for consumers that have no use for
+    /// them, such as the parser.
+    pub fn skip_doc_comments(mut self) -> Self {
+        self.skip_doc_comments = true;
+        self
+    }
+
     pub fn expect(
         &mut self,
         expected: TokenKind,
@@ -30,6 +52,15 @@ impl<'ast> Lexer<'ast> {
     ) -> Result<Token<'ast>, miette::Error> {
         match self.next() {
             Some(Ok(token)) if expected == token.kind => Ok(token),
+            Some(Ok(token))
+                if expected == TokenKind::Identifier && token.kind.is_keyword() =>
+            {
+                Err(miette::miette! {
+                    labels = vec![token.label("reserved keyword")],
+                    help = format!("choose another name, such as `{}_`", token.original),
+                    "`{}` is a reserved keyword and can't be used as a name", token.original
+                })
+            }
             Some(Ok(token)) => Err(miette::miette! {
                 labels = vec![
                     token.label(format!("expected {} here", expected))
@@ -41,7 +72,7 @@ impl<'ast> Lexer<'ast> {
             Some(Err(e)) => Err(e),
             None => Err(miette::miette! {
                 labels = vec![
-                    LabeledSpan::at_offset(self.byte_offset - 1, format!("Expected {} here", expected))
+                    LabeledSpan::at_offset(self.end_of_input(), format!("Expected {} here", expected))
                 ],
                 help = format!("{} was expected, but no more code was found", expected),
                 "unexpected end of input",
@@ -68,7 +99,7 @@ impl<'ast> Lexer<'ast> {
             Some(Err(e)) => Err(e),
             None => Err(miette::miette! {
                 labels = vec![
-                    LabeledSpan::at_offset(self.byte_offset - 1, "expected more source code here")
+                    LabeledSpan::at_offset(self.end_of_input(), "expected more source code here")
                 ],
                 help = "more source code was expected, but none was found",
                 "{unexpected}",
@@ -81,12 +112,41 @@ impl<'ast> Lexer<'ast> {
     }
 
     pub fn peek(&mut self) -> Option<&Result<Token<'ast>, miette::Error>> {
-        if self.peeked.is_some() {
-            return self.peeked.as_ref();
+        if self.peeked.is_none() {
+            let remainder = self.remainder;
+            let byte_offset = self.byte_offset;
+
+            let token = self.next();
+
+            self.peeked = Some(Peeked {
+                token,
+                remainder: self.remainder,
+                byte_offset: self.byte_offset,
+            });
+
+            self.remainder = remainder;
+            self.byte_offset = byte_offset;
+        }
+
+        self.peeked
+            .as_ref()
+            .and_then(|peeked| peeked.token.as_ref())
+    }
+
+    /// Lexes the rest of the input, returning the tokens in order along with every error found
+    /// along the way. Lexing carries on after an error, exactly as when iterating.
+    pub fn tokens(self) -> (Vec<Token<'ast>>, Vec<miette::Error>) {
+        let mut tokens = vec![];
+        let mut errors = vec![];
+
+        for token in self {
+            match token {
+                Ok(token) => tokens.push(token),
+                Err(error) => errors.push(error),
+            }
         }
 
-        self.peeked = self.next();
-        self.peeked.as_ref()
+        (tokens, errors)
     }
 
     pub fn peek_expect(
@@ -94,14 +154,367 @@ impl<'ast> Lexer<'ast> {
         expected: TokenKind,
     ) -> Option<&Result<Token<'ast>, miette::Error>> {
         match self.peek() {
-            Some(Ok(token::Token { kind, .. })) => {
-                if *kind == expected {
-                    self.peeked.as_ref()
-                } else {
-                    None
+            Some(Ok(token::Token { kind, .. })) if *kind == expected => self.peek(),
+            _ => None,
+        }
+    }
+
+    /// The offset of the last character before the end of the input, ignoring trailing
+    /// whitespace, where errors about missing code are labeled.
+    fn end_of_input(&self) -> usize {
+        self.whole[..self.byte_offset]
+            .trim_end()
+            .len()
+            .saturating_sub(1)
+    }
+
+    fn consume_char(&mut self) -> Option<char> {
+        let c = self.remainder.chars().next()?;
+        self.remainder = &self.remainder[c.len_utf8()..];
+        self.byte_offset += c.len_utf8();
+        Some(c)
+    }
+
+    /// Consumes an embedded expression after its `${`, up to and including the matching `}`.
+    /// Returns the span of the expression, or nothing when the input ends before it is closed.
+    /// The expression is lexed, so braces in its strings and characters are not counted.
+    fn consume_interpolation(&mut self) -> Option<SourceSpan> {
+        let start = self.byte_offset;
+        let mut embedded = self.slice(SourceSpan::new(start.into(), self.remainder.len()));
+        let mut depth = 0;
+
+        while let Some(token) = embedded.next() {
+            match token.map(|token| (token.kind, token.span)) {
+                Ok((TokenKind::CurlyOpen, _)) => depth += 1,
+                Ok((TokenKind::CurlyClose, span)) if depth == 0 => {
+                    let consumed = embedded.byte_offset - start;
+                    self.remainder = &self.remainder[consumed..];
+                    self.byte_offset += consumed;
+
+                    return Some(SourceSpan::new(start.into(), span.offset() - start));
+                }
+                Ok((TokenKind::CurlyClose, _)) => depth -= 1,
+                _ => {}
+            }
+        }
+
+        self.byte_offset += self.remainder.len();
+        self.remainder = "";
+
+        None
+    }
+
+    /// The offset just past the last consumed token.
+    pub fn offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// A lexer for `span` of the same input, such as an expression embedded in a string, whose
+    /// tokens have spans in the whole input.
+    pub fn slice(&self, span: SourceSpan) -> Self {
+        Self {
+            whole: self.whole,
+            remainder: &self.whole[span.offset()..span.offset() + span.len()],
+            byte_offset: span.offset(),
+            peeked: None,
+            skip_doc_comments: self.skip_doc_comments,
+        }
+    }
+
+    /// Decodes the escape sequence after a backslash; `\n`, `\r`, `\t`, `\0`, `\\`, `\"`, `\'`,
+    /// `\$`, `\x41` for a character up to `\x7F`, or `\u{1F600}` for any unicode character.
+    fn parse_escape_sequence(&mut self) -> Result<char> {
+        let start = self.byte_offset - 1;
+
+        let invalid = |lexer: &Self, help: &str| {
+            miette::miette! {
+                labels = vec![LabeledSpan::at(start..lexer.byte_offset, "this escape sequence")],
+                help = help.to_string(),
+                "invalid escape sequence"
+            }
+        };
+
+        match self.consume_char() {
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('0') => Ok('\0'),
+            Some('\\') => Ok('\\'),
+            Some('"') => Ok('"'),
+            Some('\'') => Ok('\''),
+            Some('$') => Ok('$'),
+            Some('x') => {
+                let digits = self.remainder.get(..2).unwrap_or_default();
+
+                match u8::from_str_radix(digits, 16) {
+                    Ok(value) if value <= 0x7F && digits.chars().all(|c| c.is_ascii_hexdigit()) => {
+                        self.remainder = &self.remainder[2..];
+                        self.byte_offset += 2;
+                        Ok(value as char)
+                    }
+                    _ => Err(invalid(
+                        self,
+                        "expected two hexadecimal digits up to `7F`, like `\\x41`",
+                    )),
+                }
+            }
+            Some('u') => {
+                let help = "expected up to six hexadecimal digits in braces, like `\\u{1F600}`";
+
+                if !self.remainder.starts_with('{') {
+                    return Err(invalid(self, help));
+                }
+
+                let end = match self.remainder.find('}') {
+                    Some(end) => end,
+                    None => return Err(invalid(self, help)),
+                };
+
+                let digits = &self.remainder[1..end];
+                self.remainder = &self.remainder[end + 1..];
+                self.byte_offset += end + 1;
+
+                if digits.is_empty()
+                    || digits.len() > 6
+                    || !digits.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    return Err(invalid(self, help));
+                }
+
+                let value = u32::from_str_radix(digits, 16).unwrap_or(u32::MAX);
+
+                char::from_u32(value).ok_or_else(|| {
+                    let help = if value > 0x10FFFF {
+                        "the largest code point is `10FFFF`".to_string()
+                    } else {
+                        format!("`{:X}` is a surrogate code point, which is not a character on its own", value)
+                    };
+
+                    miette::miette! {
+                        labels = vec![LabeledSpan::at(start..self.byte_offset, "this escape sequence")],
+                        help = help,
+                        "invalid or surrogate code point"
+                    }
+                })
+            }
+            Some(c) => Err(invalid(
+                self,
+                &format!(
+                    "`\\{}` is not an escape sequence, use `\\\\` for a backslash",
+                    c
+                ),
+            )),
+            None => Err(invalid(
+                self,
+                "expected an escape sequence after the backslash",
+            )),
+        }
+    }
+
+    /// Parses a raw string after its `r`. Its contents are taken as written, without escapes or
+    /// interpolation, up to a quote followed by as many `#` as the opening quote was preceded by.
+    fn parse_raw_string(&mut self, start: usize) -> Result<(TokenKind, TokenValue<'ast>)> {
+        let hashes = self.remainder.len() - self.remainder.trim_start_matches('#').len();
+        let closing = format!("\"{}", "#".repeat(hashes));
+
+        self.remainder = &self.remainder[hashes + 1..];
+        self.byte_offset += hashes + 1;
+
+        match self.remainder.find(&closing) {
+            Some(len) => {
+                let string = &self.remainder[..len];
+                self.remainder = &self.remainder[len + closing.len()..];
+                self.byte_offset += len + closing.len();
+
+                Ok((TokenKind::String, TokenValue::String(string.into())))
+            }
+            None => {
+                self.byte_offset += self.remainder.len();
+                self.remainder = "";
+
+                Err(miette::miette! {
+                    labels = vec![LabeledSpan::at(start..start + hashes + 2, "this raw string")],
+                    help = format!("close the string with `{}`", closing),
+                    "unterminated raw string"
+                })
+            }
+        }
+    }
+
+    /// Parses a multi-line string after its first quote. The lines between the opening and
+    /// closing `"""` are its contents, without the indentation of the closing `"""`, so the string
+    /// can be indented along with the code around it. Escapes work as in other strings.
+    fn parse_multiline_string(&mut self, start: usize) -> Result<(TokenKind, TokenValue<'ast>)> {
+        self.remainder = &self.remainder[2..];
+        self.byte_offset += 2;
+        let content_start = self.byte_offset;
+
+        let mut content_end = None;
+        while let Some(c) = self.consume_char() {
+            match c {
+                '\\' => {
+                    self.consume_char();
+                }
+                '"' if self.remainder.starts_with("\"\"") => {
+                    content_end = Some(self.byte_offset - 1);
+                    self.remainder = &self.remainder[2..];
+                    self.byte_offset += 2;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let Some(content_end) = content_end else {
+            return Err(miette::miette! {
+                labels = vec![LabeledSpan::at(start..start + 3, "this string")],
+                help = "close the string with `\"\"\"`, or write `\"\" \"` for an empty string followed by another string",
+                "unterminated multi-line string"
+            });
+        };
+
+        let mut lines = vec![];
+        let mut offset = content_start;
+        for line in self.whole[content_start..content_end].split('\n') {
+            lines.push((offset, line.strip_suffix('\r').unwrap_or(line)));
+            offset += line.len() + 1;
+        }
+
+        // Blank lines right after the opening quotes and before the closing ones are not part of
+        // the string, and the latter holds the indentation of the closing quotes
+        let mut indentation = "";
+        if lines.len() > 1 {
+            if lines[0].1.trim().is_empty() {
+                lines.remove(0);
+            }
+            if lines.last().is_some_and(|(_, line)| line.trim().is_empty()) {
+                indentation = lines.pop().unwrap().1;
+            }
+        }
+
+        let mut string = String::new();
+        for (i, (offset, line)) in lines.into_iter().enumerate() {
+            if i > 0 {
+                string.push('\n');
+            }
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if !line.starts_with(indentation) {
+                let whitespace = line.len() - line.trim_start().len();
+                return Err(miette::miette! {
+                    labels = vec![
+                        LabeledSpan::at(offset..offset + whitespace.max(1), "this line"),
+                        LabeledSpan::at(content_end..content_end + 3, "closing quotes"),
+                    ],
+                    help = "indent every line at least as far as the closing `\"\"\"`",
+                    "insufficient indentation in multi-line string"
+                });
+            }
+
+            let offset = offset + indentation.len();
+            let mut line = self.slice(SourceSpan::new(
+                offset.into(),
+                line.len() - indentation.len(),
+            ));
+            while let Some(c) = line.consume_char() {
+                match c {
+                    '\\' => string.push(line.parse_escape_sequence()?),
+                    c => string.push(c),
                 }
             }
-            _ => None,
+        }
+
+        Ok((TokenKind::String, TokenValue::String(string.into())))
+    }
+
+    /// Skips a block comment after its `/`, up to the `*/` that closes it. Block comments nest,
+    /// so `/* a /* b */ c */` is a single comment.
+    fn skip_block_comment(&mut self, start: usize) -> Result<()> {
+        self.consume_char();
+        let mut depth = 1;
+
+        while let Some(c) = self.consume_char() {
+            match c {
+                '/' if self.remainder.starts_with('*') => {
+                    self.consume_char();
+                    depth += 1;
+                }
+                '*' if self.remainder.starts_with('/') => {
+                    self.consume_char();
+                    depth -= 1;
+
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Err(miette::miette! {
+            labels = vec![LabeledSpan::at(start..start + 2, "this comment")],
+            help = "close the comment with `*/`, once for every `/*` in it",
+            "unterminated block comment"
+        })
+    }
+
+    /// Parses the digits and suffix of a hexadecimal, octal or binary integer such as `0xFF`,
+    /// after its prefix.
+    fn parse_radix_integer(
+        &mut self,
+        radix: u32,
+        start: usize,
+    ) -> Result<(TokenKind, TokenValue<'ast>)> {
+        let (name, valid_digits) = match radix {
+            16 => ("hexadecimal", "0 to 9 and A to F"),
+            8 => ("octal", "0 to 7"),
+            _ => ("binary", "0 and 1"),
+        };
+
+        let len = self
+            .remainder
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(self.remainder.len());
+        let literal = &self.remainder[..len];
+        self.remainder = &self.remainder[len..];
+        self.byte_offset += len;
+
+        let span = start..self.byte_offset;
+        let (digits, suffix) =
+            literal.split_at(literal.find(|c: char| !c.is_digit(radix)).unwrap_or(len));
+
+        let text = &self.whole[start..span.end - suffix.len()];
+        let suffix = match suffix {
+            "" => Some(None),
+            suffix => IntegerSuffix::parse(suffix).map(Some),
+        };
+
+        let suffix = match suffix.filter(|_| !digits.is_empty()) {
+            Some(suffix) => suffix,
+            None => {
+                return Err(miette::miette! {
+                    labels = vec![LabeledSpan::at(span, "this number")],
+                    help = format!("{} numbers only use the digits {}", name, valid_digits),
+                    "invalid {} number", name
+                })
+            }
+        };
+
+        match suffix {
+            Some(suffix) => {
+                suffixed_integer(i128::from_str_radix(digits, radix).ok(), suffix, text, span)
+            }
+            None => match i64::from_str_radix(digits, radix) {
+                Ok(value) => Ok((TokenKind::Integer, TokenValue::Integer(value))),
+                Err(_) => Err(miette::miette! {
+                    labels = vec![LabeledSpan::at(span, "this number")],
+                    help = format!("{} does not fit in a 64-bit integer", text),
+                    "integer literal out of range"
+                }),
+            },
         }
     }
 
@@ -129,10 +542,23 @@ impl<'ast> Iterator for Lexer<'ast> {
     type Item = Result<Token<'ast>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(next) = self.peeked.take() {
-            return Some(next);
+        if let Some(peeked) = self.peeked.take() {
+            self.remainder = peeked.remainder;
+            self.byte_offset = peeked.byte_offset;
+            return peeked.token;
         }
 
+        loop {
+            match self.lex_token()? {
+                Ok(token) if self.skip_doc_comments && token.kind == TokenKind::DocComment => {}
+                token => return Some(token),
+            }
+        }
+    }
+}
+
+impl<'ast> Lexer<'ast> {
+    fn lex_token(&mut self) -> Option<Result<Token<'ast>>> {
         let mut chars = self.remainder.chars();
 
         let start_offset = self.byte_offset;
@@ -158,10 +584,38 @@ impl<'ast> Iterator for Lexer<'ast> {
             '^' => Ok((TokenKind::Caret, TokenValue::None)),
             '~' => Ok((TokenKind::Tilde, TokenValue::None)),
             '?' => Ok((TokenKind::Question, TokenValue::None)),
-            ':' => Ok((TokenKind::Colon, TokenValue::None)),
+            ':' => self.parse_compound_operator(TokenKind::Colon, TokenKind::DoubleColon, ':'),
             '-' => self.parse_compound_operator(TokenKind::Minus, TokenKind::Arrow, '>'),
             '+' => Ok((TokenKind::Plus, TokenValue::None)),
             '*' => Ok((TokenKind::Star, TokenValue::None)),
+            '/' if self.remainder.starts_with('/') => {
+                let len = self.remainder.find('\n').unwrap_or(self.remainder.len());
+                let comment = &self.remainder[1..len];
+                self.remainder = &self.remainder[len..];
+                self.byte_offset += len;
+
+                // `///` is a doc comment, but `////` is a plain comment again
+                match comment.strip_prefix('/') {
+                    Some(text) if !text.starts_with('/') => Ok((
+                        TokenKind::DocComment,
+                        TokenValue::DocComment(text.trim_end_matches('\r').into()),
+                    )),
+                    _ => return self.next(),
+                }
+            }
+            '/' if self.remainder.starts_with('*') => match self.skip_block_comment(start_offset) {
+                // `/**` is a doc comment, but `/**/` and `/***` are plain comments
+                Ok(()) => match &self.whole[start_offset + 2..self.byte_offset - 2] {
+                    text if text.starts_with('*') && !text.starts_with("**") && text != "*" => {
+                        Ok((
+                            TokenKind::DocComment,
+                            TokenValue::DocComment(text[1..].into()),
+                        ))
+                    }
+                    _ => return self.next(),
+                },
+                Err(error) => Err(error),
+            },
             '/' => Ok((TokenKind::Slash, TokenValue::None)),
             '%' => Ok((TokenKind::Percent, TokenValue::None)),
             '=' => self.parse_compound_operator(TokenKind::Equal, TokenKind::Equality, '='),
@@ -174,12 +628,16 @@ impl<'ast> Iterator for Lexer<'ast> {
                 TokenKind::GreaterThanOrEqual,
                 '=',
             ),
-            'a'..='z' | 'A'..='Z' | '_' => {
+            // Raw strings, like `r"C:\temp"` or `r#"say "hi""#`
+            'r' if self.remainder.trim_start_matches('#').starts_with('"') => {
+                self.parse_raw_string(start_offset)
+            }
+            c if is_identifier_start(c) => {
                 // Identifiers
                 let mut ident = String::new();
                 ident.push(c);
                 while let Some(c) = self.remainder.chars().next() {
-                    if c.is_alphanumeric() || c == '_' {
+                    if is_identifier_continue(c) {
                         ident.push(c);
                         self.remainder = &self.remainder[c.len_utf8()..];
                         self.byte_offset += c.len_utf8();
@@ -191,6 +649,8 @@ impl<'ast> Iterator for Lexer<'ast> {
                 match ident.as_str() {
                     "if" => Ok((TokenKind::If, TokenValue::None)),
                     "else" => Ok((TokenKind::Else, TokenValue::None)),
+                    "while" => Ok((TokenKind::While, TokenValue::None)),
+                    "for" => Ok((TokenKind::For, TokenValue::None)),
                     "fn" => Ok((TokenKind::Function, TokenValue::None)),
                     "true" => Ok((TokenKind::Boolean, TokenValue::Boolean(true))),
                     "false" => Ok((TokenKind::Boolean, TokenValue::Boolean(false))),
@@ -200,17 +660,29 @@ impl<'ast> Iterator for Lexer<'ast> {
                     "enum" => Ok((TokenKind::Enum, TokenValue::None)),
                     "trait" => Ok((TokenKind::Trait, TokenValue::None)),
                     "bool" => Ok((TokenKind::BooleanType, TokenValue::None)),
-                    "int" => Ok((TokenKind::IntegerType, TokenValue::None)),
+                    "int" | "i32" => Ok((TokenKind::IntegerType, TokenValue::None)),
+                    "i64" => Ok((TokenKind::LongType, TokenValue::None)),
                     "dec" => Ok((TokenKind::DecimalType, TokenValue::None)),
                     "str" => Ok((TokenKind::StringType, TokenValue::None)),
                     "char" => Ok((TokenKind::CharacterType, TokenValue::None)),
                     "return" => Ok((TokenKind::Return, TokenValue::None)),
                     ident => Ok((
                         TokenKind::Identifier,
-                        TokenValue::Identifier(ident.to_string().into()),
+                        TokenValue::Identifier(
+                            ident.to_string().into(),
+                            SourceSpan::new(start_offset.into(), self.byte_offset - start_offset),
+                        ),
                     )),
                 }
             }
+            // Hexadecimal, octal and binary numbers
+            '0' if radix(self.remainder).is_some() => {
+                let radix = radix(self.remainder).unwrap();
+                self.remainder = &self.remainder[1..];
+                self.byte_offset += 1;
+
+                self.parse_radix_integer(radix, start_offset)
+            }
             // Whole and decimal numbers
             '0'..='9' => {
                 let mut number = String::new();
@@ -225,7 +697,76 @@ impl<'ast> Iterator for Lexer<'ast> {
                     }
                 }
 
-                if let Ok(num) = number.parse::<i64>() {
+                // An exponent, like the `e10` in `1e10` or the `E-4` in `2.5E-4`
+                let missing_exponent = match exponent(self.remainder) {
+                    Some(Ok(len)) => {
+                        number.push_str(&self.remainder[..len]);
+                        self.remainder = &self.remainder[len..];
+                        self.byte_offset += len;
+                        false
+                    }
+                    Some(Err(len)) => {
+                        self.remainder = &self.remainder[len..];
+                        self.byte_offset += len;
+                        true
+                    }
+                    None => false,
+                };
+
+                // Letters directly after a number are only a suffix when they look like one,
+                // otherwise they start an identifier, like the `if` in `5if`
+                let suffix_len = self
+                    .remainder
+                    .find(|c| !is_identifier_continue(c))
+                    .unwrap_or(self.remainder.len());
+                let suffix = &self.remainder[..suffix_len];
+                let is_suffix = NUMBER_SUFFIXES.contains(&suffix) || is_width_suffix(suffix);
+
+                if missing_exponent {
+                    Err(miette::miette! {
+                        labels = vec![LabeledSpan::at(start_offset..self.byte_offset, "this number")],
+                        help = "add the digits of the exponent, like `1e10`",
+                        "missing exponent"
+                    })
+                } else if is_suffix {
+                    self.remainder = &self.remainder[suffix_len..];
+                    self.byte_offset += suffix_len;
+
+                    let span = self.byte_offset - number.len() - suffix_len..self.byte_offset;
+                    match (suffix, IntegerSuffix::parse(suffix), number.parse::<i128>()) {
+                        (_, Some(suffix), Ok(num)) => {
+                            suffixed_integer(Some(num), suffix, &number, span)
+                        }
+                        (_, Some(suffix), Err(_)) if !number.contains(['.', 'e', 'E']) => {
+                            suffixed_integer(None, suffix, &number, span)
+                        }
+                        (_, Some(_), Err(_)) => Err(miette::miette! {
+                            labels = vec![LabeledSpan::at(span, "this number")],
+                            help = "only whole numbers can have an integer suffix",
+                            "invalid number suffix"
+                        }),
+                        ("f64", _, _) => match number.parse::<f64>() {
+                            Ok(num) => Ok((TokenKind::Decimal, TokenValue::Decimal(num))),
+                            Err(_) => Err(miette::miette! {
+                                labels = vec![LabeledSpan::at(span, "this number")],
+                                "invalid number"
+                            }),
+                        },
+                        (suffix, _, _) => Err(miette::miette! {
+                            labels = vec![LabeledSpan::at(span, "this number")],
+                            help = format!(
+                                "`{}` is not a number suffix, use one of {}",
+                                suffix,
+                                NUMBER_SUFFIXES
+                                    .iter()
+                                    .map(|suffix| format!("`{}`", suffix))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ),
+                            "invalid number suffix"
+                        }),
+                    }
+                } else if let Ok(num) = number.parse::<i64>() {
                     Ok((TokenKind::Integer, TokenValue::Integer(num)))
                 } else if let Ok(num) = number.parse::<f64>() {
                     Ok((TokenKind::Decimal, TokenValue::Decimal(num)))
@@ -238,49 +779,133 @@ impl<'ast> Iterator for Lexer<'ast> {
                     })
                 }
             }
+            // Three quotes always open a multi-line string, even where they could be read as an
+            // empty string followed by another string; those have to be separated, as in `"" "a"`
+            '"' if self.remainder.starts_with("\"\"") => self.parse_multiline_string(start_offset),
             '"' => {
                 let mut string = String::new();
-                while let Some(c) = self.remainder.chars().next() {
-                    if c == '"' {
-                        self.remainder = &self.remainder[c.len_utf8()..];
-                        self.byte_offset += c.len_utf8();
-                        break;
-                    } else {
-                        string.push(c);
-                        self.remainder = &self.remainder[c.len_utf8()..];
-                        self.byte_offset += c.len_utf8();
+                let mut parts = vec![];
+                let mut error = None;
+                let mut fragment_start = self.byte_offset;
+
+                // The whole string is consumed even when an escape is invalid, so lexing resumes
+                // after it
+                while let Some(c) = self.consume_char() {
+                    match c {
+                        '"' => break,
+                        '$' if self.remainder.starts_with('{') => {
+                            let start = self.byte_offset - 1;
+                            self.consume_char();
+
+                            match self.consume_interpolation() {
+                                Some(span) => {
+                                    if !string.is_empty() {
+                                        parts.push(StringPart::Fragment(
+                                            std::mem::take(&mut string).into(),
+                                            SourceSpan::new(
+                                                fragment_start.into(),
+                                                start - fragment_start,
+                                            ),
+                                        ));
+                                    }
+                                    parts.push(StringPart::Expression(span));
+                                    fragment_start = self.byte_offset;
+                                }
+                                None => {
+                                    error.get_or_insert(miette::miette! {
+                                        labels = vec![LabeledSpan::at(start..self.byte_offset, "this interpolation")],
+                                        help = "close the embedded expression with `}`, or write `\\${` for a literal `${`",
+                                        "unterminated interpolation"
+                                    });
+                                }
+                            }
+                        }
+                        '\\' => match self.parse_escape_sequence() {
+                            Ok(c) => string.push(c),
+                            Err(err) => {
+                                error.get_or_insert(err);
+                            }
+                        },
+                        c => string.push(c),
                     }
                 }
 
-                Ok((TokenKind::String, TokenValue::String(string.into())))
+                match error {
+                    Some(error) => Err(error),
+                    None if parts.is_empty() => {
+                        Ok((TokenKind::String, TokenValue::String(string.into())))
+                    }
+                    None => {
+                        if !string.is_empty() {
+                            // Up to the closing quote
+                            parts.push(StringPart::Fragment(
+                                string.into(),
+                                SourceSpan::new(
+                                    fragment_start.into(),
+                                    self.byte_offset - 1 - fragment_start,
+                                ),
+                            ));
+                        }
+
+                        Ok((
+                            TokenKind::InterpolatedString,
+                            TokenValue::InterpolatedString(parts),
+                        ))
+                    }
+                }
             }
             '\'' => {
-                let c = self.remainder.chars().next()?;
-                self.remainder = &self.remainder[c.len_utf8()..];
-                self.byte_offset += c.len_utf8();
+                let c = match self.consume_char()? {
+                    '\\' => self.parse_escape_sequence(),
+                    c => Ok(c),
+                };
+
+                match c {
+                    Ok(c) if self.remainder.starts_with('\'') => {
+                        self.consume_char();
+                        Ok((TokenKind::Character, TokenValue::Character(c)))
+                    }
+                    Ok(_) => Err(miette::miette! {
+                        labels = vec![
+                            LabeledSpan::at(start_offset..self.byte_offset, "this character")
+                        ],
+                        "expected closing single quote"
+                    }),
+                    Err(err) => Err(err),
+                }
+            }
+            ' ' | '\r' | '\t' | '\n' => {
+                return self.next();
+            }
+            _ => {
+                // Coalesce a run of unexpected characters into a single error
+                while let Some(c) = self.remainder.chars().next() {
+                    if starts_token(c) {
+                        break;
+                    }
 
-                if self.remainder.chars().next()? == '\'' {
                     self.remainder = &self.remainder[c.len_utf8()..];
                     self.byte_offset += c.len_utf8();
-                    Ok((TokenKind::Character, TokenValue::Character(c)))
+                }
+
+                let run = &self.whole[start_offset..self.byte_offset];
+
+                if run.chars().count() == 1 {
+                    Err(miette::miette! {
+                        labels = vec![
+                            LabeledSpan::at(start_offset..self.byte_offset, "this character")
+                        ],
+                        "unexpected character '{c}' in input"
+                    })
                 } else {
                     Err(miette::miette! {
                         labels = vec![
-                            LabeledSpan::at(self.byte_offset..self.byte_offset + c.len_utf8(), "this character")
+                            LabeledSpan::at(start_offset..self.byte_offset, "these characters")
                         ],
-                        "expected closing single quote"
+                        "unexpected characters '{run}' in input"
                     })
                 }
             }
-            ' ' | '\r' | '\t' | '\n' => {
-                return self.next();
-            }
-            _ => Err(miette::miette! {
-                labels = vec![
-                    LabeledSpan::at(self.byte_offset - c.len_utf8()..self.byte_offset, "this character")
-                ],
-                "unexpected character '{c}' in input"
-            }),
         };
 
         let byte_length = self
@@ -296,3 +921,112 @@ impl<'ast> Iterator for Lexer<'ast> {
         }))
     }
 }
+
+/// Whether a character can start a token, or is whitespace separating tokens.
+fn starts_token(c: char) -> bool {
+    matches!(
+        c,
+        '(' | ')'
+            | '{'
+            | '}'
+            | '['
+            | ']'
+            | ';'
+            | ','
+            | '.'
+            | '@'
+            | '#'
+            | '$'
+            | '|'
+            | '^'
+            | '~'
+            | '?'
+            | ':'
+            | '-'
+            | '+'
+            | '*'
+            | '/'
+            | '%'
+            | '='
+            | '!'
+            | '<'
+            | '>'
+            | '"'
+            | '\''
+            | ' '
+            | '\r'
+            | '\t'
+            | '\n'
+    ) || c.is_ascii_digit()
+        || is_identifier_start(c)
+}
+
+/// Whether a character can start an identifier. Identifiers follow UAX #31: they start with an
+/// `XID_Start` character or an underscore, so `café` and `名前` are valid names.
+fn is_identifier_start(c: char) -> bool {
+    c == '_' || unicode_ident::is_xid_start(c)
+}
+
+/// Whether a character can continue an identifier, being any `XID_Continue` character.
+fn is_identifier_continue(c: char) -> bool {
+    unicode_ident::is_xid_continue(c)
+}
+
+/// The token of an integer with a suffix, or an error when its value does not fit the type of the
+/// suffix. `value` is nothing when the integer is too large to even be read. A value that only
+/// fits once negated, like the `128` of `-128i8`, is left to the parser, which sees the minus.
+fn suffixed_integer<'ast>(
+    value: Option<i128>,
+    suffix: IntegerSuffix,
+    text: &str,
+    span: std::ops::Range<usize>,
+) -> Result<(TokenKind, TokenValue<'ast>)> {
+    match value {
+        Some(value) if suffix.range().contains(&value) || suffix.range().contains(&-value) => Ok((
+            TokenKind::Integer,
+            TokenValue::SuffixedInteger(value, suffix),
+        )),
+        _ => Err(suffix.out_of_range(text, span.into())),
+    }
+}
+
+/// The length of the exponent at the start of `input`, such as `e10` or `E-4`, or as the error
+/// the length of an exponent without digits. An `e` followed by letters is not an exponent but
+/// the start of a name, like the `else` in `5else`.
+fn exponent(input: &str) -> Option<std::result::Result<usize, usize>> {
+    let rest = input.strip_prefix(['e', 'E'])?;
+    let sign = usize::from(rest.starts_with(['+', '-']));
+    let digits = rest[sign..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len() - sign);
+
+    if digits > 0 {
+        Some(Ok(1 + sign + digits))
+    } else if sign > 0 || !rest.chars().next().is_some_and(is_identifier_continue) {
+        Some(Err(1 + sign))
+    } else {
+        None
+    }
+}
+
+/// The radix of a number starting with `0` followed by `input`, when `input` starts with a
+/// `x`, `o` or `b` prefix and a digit.
+fn radix(input: &str) -> Option<u32> {
+    let radix = match input.as_bytes() {
+        [b'x', next, ..] if next.is_ascii_alphanumeric() => 16,
+        [b'o', next, ..] if next.is_ascii_alphanumeric() => 8,
+        [b'b', next, ..] if next.is_ascii_alphanumeric() => 2,
+        _ => return None,
+    };
+
+    Some(radix)
+}
+
+/// Whether `suffix` names a type by its width, like `u8` or `f32`, whether or not that type
+/// exists.
+fn is_width_suffix(suffix: &str) -> bool {
+    let mut chars = suffix.chars();
+    matches!(chars.next(), Some('i' | 'u' | 'f'))
+        && !chars.as_str().is_empty()
+        && chars.all(|c| c.is_ascii_digit())
+}
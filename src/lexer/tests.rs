@@ -1,4 +1,5 @@
 use super::*;
+use crate::ast::{Position, DEFAULT_TAB_WIDTH};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -31,6 +32,136 @@ fn punctuation() {
     );
 }
 
+#[test]
+fn double_colon() {
+    test_tokens_eq(
+        Lexer::new("std::print : ::"),
+        vec![
+            (TokenKind::Identifier, identifier("std", 0)),
+            (TokenKind::DoubleColon, TokenValue::None),
+            (TokenKind::Identifier, identifier("print", 5)),
+            (TokenKind::Colon, TokenValue::None),
+            (TokenKind::DoubleColon, TokenValue::None),
+        ],
+    );
+}
+
+#[test]
+fn integer_suffixes() {
+    test_tokens_eq(
+        Lexer::new("5i 7l 6i32 8i64 2f64 2.5f64 12 5if"),
+        vec![
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(5, IntegerSuffix::Integer),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(7, IntegerSuffix::Long),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(6, IntegerSuffix::Integer),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(8, IntegerSuffix::Long),
+            ),
+            (TokenKind::Decimal, TokenValue::Decimal(2.0)),
+            (TokenKind::Decimal, TokenValue::Decimal(2.5)),
+            (TokenKind::Integer, TokenValue::Integer(12)),
+            (TokenKind::Integer, TokenValue::Integer(5)),
+            (TokenKind::If, TokenValue::None),
+        ],
+    );
+}
+
+#[test]
+fn invalid_integer_suffixes() {
+    let errors = Lexer::new("3000000000i 1.5l 3000000000i32 10000000000000000000l 5u128")
+        .map(|token| token.unwrap_err().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        [
+            "integer literal out of range",
+            "invalid number suffix",
+            "integer literal out of range",
+            "integer literal out of range",
+            "invalid number suffix"
+        ]
+    );
+
+    let error = Lexer::new("5u128").next().unwrap().unwrap_err();
+    assert_eq!(
+        error.help().unwrap().to_string(),
+        "`u128` is not a number suffix, use one of `i`, `l`, `i8`, `i16`, `i32`, `i64`, `u8`, `u16`, `u32`, `u64`, `f64`"
+    );
+}
+
+#[test]
+fn escape_sequences() {
+    test_tokens_eq(
+        Lexer::new(r#""line\n\tindented \x41 \u{1F600} \"quoted\" \\" '\n' '\'' '\u{e9}'"#),
+        vec![
+            (
+                TokenKind::String,
+                TokenValue::String("line\n\tindented A \u{1F600} \"quoted\" \\".into()),
+            ),
+            (TokenKind::Character, TokenValue::Character('\n')),
+            (TokenKind::Character, TokenValue::Character('\'')),
+            (TokenKind::Character, TokenValue::Character('é')),
+        ],
+    );
+}
+
+#[test]
+fn escape_sequences_keep_original_text() {
+    let source = r#"let s = "a\nb\tc\x41";"#;
+
+    let printed = Lexer::new(source)
+        .map(Result::unwrap)
+        .map(|token| token.original)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(" ;", ";");
+
+    assert_eq!(printed, source);
+}
+
+#[test]
+fn invalid_escape_sequences() {
+    let mut lexer = Lexer::new(r#""\q" "\x80" "\u{}" after"#);
+
+    for _ in 0..3 {
+        let error = lexer.next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), "invalid escape sequence");
+    }
+
+    let after = lexer.next().unwrap().unwrap();
+    assert_eq!(after.kind, TokenKind::Identifier);
+}
+
+#[test]
+fn code_point_escapes() {
+    test_tokens_eq(
+        Lexer::new(r#""\u{0}" "\u{10FFFF}""#),
+        vec![
+            (TokenKind::String, TokenValue::String("\0".into())),
+            (TokenKind::String, TokenValue::String("\u{10FFFF}".into())),
+        ],
+    );
+
+    for code in [r#""\u{D800}""#, r#""\u{110000}""#] {
+        let error = Lexer::new(code).next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), "invalid or surrogate code point");
+    }
+
+    let error = Lexer::new(r#""\u{DFFF}""#).next().unwrap().unwrap_err();
+    assert!(error.help().unwrap().to_string().contains("surrogate"));
+}
+
 #[test]
 fn comparison() {
     test_tokens_eq(
@@ -50,10 +181,12 @@ fn comparison() {
 #[test]
 fn keywords() {
     test_tokens_eq(
-        Lexer::new("if else"),
+        Lexer::new("if else while for"),
         vec![
             (TokenKind::If, TokenValue::None),
             (TokenKind::Else, TokenValue::None),
+            (TokenKind::While, TokenValue::None),
+            (TokenKind::For, TokenValue::None),
         ],
     );
 }
@@ -110,13 +243,36 @@ fn identifiers() {
     test_tokens_eq(
         Lexer::new("foo bar baz"),
         vec![
-            (TokenKind::Identifier, TokenValue::Identifier("foo".into())),
-            (TokenKind::Identifier, TokenValue::Identifier("bar".into())),
-            (TokenKind::Identifier, TokenValue::Identifier("baz".into())),
+            (TokenKind::Identifier, identifier("foo", 0)),
+            (TokenKind::Identifier, identifier("bar", 4)),
+            (TokenKind::Identifier, identifier("baz", 8)),
+        ],
+    );
+}
+
+#[test]
+fn unicode_identifiers() {
+    test_tokens_eq(
+        Lexer::new("café 名前 _x1 if"),
+        vec![
+            (TokenKind::Identifier, identifier("café", 0)),
+            (TokenKind::Identifier, identifier("名前", 6)),
+            (TokenKind::Identifier, identifier("_x1", 13)),
+            (TokenKind::If, TokenValue::None),
         ],
     );
 }
 
+#[test]
+fn identifier_spans() {
+    for token in Lexer::new("let café = 名前 + x;").map(Result::unwrap) {
+        if let TokenValue::Identifier(name, span) = token.value {
+            assert_eq!(span, token.span);
+            assert_eq!(name, token.original);
+        }
+    }
+}
+
 #[test]
 fn program() {
     let program = "
@@ -128,20 +284,14 @@ fn program() {
     let lexer = Lexer::new(program);
     let expected_tokens = vec![
         (TokenKind::Function, TokenValue::None),
-        (TokenKind::Identifier, TokenValue::Identifier("main".into())),
+        (TokenKind::Identifier, identifier("main", 16)),
         (TokenKind::ParenOpen, TokenValue::None),
-        (TokenKind::Identifier, TokenValue::Identifier("self".into())),
+        (TokenKind::Identifier, identifier("self", 21)),
         (TokenKind::ParenClose, TokenValue::None),
         (TokenKind::Tilde, TokenValue::None),
-        (
-            TokenKind::Identifier,
-            TokenValue::Identifier("number".into()),
-        ),
+        (TokenKind::Identifier, identifier("number", 29)),
         (TokenKind::CurlyOpen, TokenValue::None),
-        (
-            TokenKind::Identifier,
-            TokenValue::Identifier("print".into()),
-        ),
+        (TokenKind::Identifier, identifier("print", 54)),
         (TokenKind::ParenOpen, TokenValue::None),
         (
             TokenKind::String,
@@ -187,6 +337,135 @@ fn peeking() {
     assert_eq!(third.value, TokenValue::Integer(3));
 }
 
+#[test]
+fn unexpected_characters() {
+    let mut lexer = Lexer::new("123±±±456");
+
+    let first = lexer.next().unwrap().unwrap();
+    assert_eq!(first.value, TokenValue::Integer(123));
+
+    let error = lexer.next().unwrap().unwrap_err();
+    let labels = error.labels().unwrap().collect::<Vec<_>>();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].offset(), 3);
+    assert_eq!(labels[0].len(), "±±±".len());
+
+    let second = lexer.next().unwrap().unwrap();
+    assert_eq!(second.value, TokenValue::Integer(456));
+
+    assert!(lexer.next().is_none());
+}
+
+#[test]
+fn peeking_keeps_position() {
+    let mut lexer = Lexer::new("1 ±");
+
+    lexer.next().unwrap().unwrap();
+
+    let peeked = lexer.peek().unwrap().as_ref().unwrap_err();
+    assert_eq!(peeked.labels().unwrap().next().unwrap().offset(), 2);
+
+    let error = lexer.next().unwrap().unwrap_err();
+    assert_eq!(error.labels().unwrap().next().unwrap().offset(), 2);
+}
+
+#[test]
+fn peeking_at_end_of_input() {
+    let mut lexer = Lexer::new("let   ");
+
+    lexer.next().unwrap().unwrap();
+    assert!(lexer.peek().is_none());
+
+    let error = lexer
+        .expect(TokenKind::Identifier, "expected a variable name")
+        .unwrap_err();
+    assert_eq!(error.labels().unwrap().next().unwrap().offset(), 2);
+}
+
+#[test]
+fn crlf_positions() {
+    let lf = "fn main() {\n    let a = 1;\n    a\n}\n";
+    let crlf = lf.replace('\n', "\r\n");
+
+    let positions = |source: &str| {
+        Lexer::new(source)
+            .map(Result::unwrap)
+            .map(|token| {
+                let start = Position::from_offset(source, token.span.offset());
+                let end = Position::from_offset(source, token.span.offset() + token.span.len());
+                assert_eq!(start.to_offset(source), Some(token.span.offset()));
+                (token.kind, start, end)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(positions(lf), positions(&crlf));
+    assert_eq!(
+        Position {
+            line: 2,
+            column: 15
+        }
+        .to_offset(&crlf),
+        crlf.find(";\r\n").map(|i| i + 1)
+    );
+}
+
+#[test]
+fn interpolated_strings() {
+    test_tokens_eq(
+        Lexer::new(r#""x = ${x + 1}!" "\${x}""#),
+        vec![
+            (
+                TokenKind::InterpolatedString,
+                TokenValue::InterpolatedString(vec![
+                    StringPart::Fragment("x = ".into(), SourceSpan::new(1.into(), 4)),
+                    StringPart::Expression(SourceSpan::new(7.into(), 5)),
+                    StringPart::Fragment("!".into(), SourceSpan::new(13.into(), 1)),
+                ]),
+            ),
+            (TokenKind::String, TokenValue::String("${x}".into())),
+        ],
+    );
+
+    test_tokens_eq(
+        Lexer::new(r#""a ${ "}" + '}' } b""#),
+        vec![(
+            TokenKind::InterpolatedString,
+            TokenValue::InterpolatedString(vec![
+                StringPart::Fragment("a ".into(), SourceSpan::new(1.into(), 2)),
+                StringPart::Expression(SourceSpan::new(5.into(), 11)),
+                StringPart::Fragment(" b".into(), SourceSpan::new(17.into(), 2)),
+            ]),
+        )],
+    );
+
+    let error = Lexer::new(r#""${x""#).next().unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "unterminated interpolation");
+}
+
+#[test]
+fn drain_tokens() {
+    let (tokens, errors) = Lexer::new("let a = 3000000000i;").tokens();
+
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| (token.kind.clone(), token.span))
+            .collect::<Vec<_>>(),
+        vec![
+            (TokenKind::Let, SourceSpan::new(0.into(), 3)),
+            (TokenKind::Identifier, SourceSpan::new(4.into(), 1)),
+            (TokenKind::Equal, SourceSpan::new(6.into(), 1)),
+            (TokenKind::Semicolon, SourceSpan::new(19.into(), 1)),
+        ]
+    );
+    assert_eq!(errors.len(), 1);
+}
+
+fn identifier(name: &str, offset: usize) -> TokenValue<'_> {
+    TokenValue::Identifier(name.into(), SourceSpan::new(offset.into(), name.len()))
+}
+
 fn test_tokens_eq(lexer: Lexer<'_>, tokens: Vec<(TokenKind, TokenValue<'_>)>) {
     let actual_tokens = lexer
         .map(Result::unwrap)
@@ -195,3 +474,308 @@ fn test_tokens_eq(lexer: Lexer<'_>, tokens: Vec<(TokenKind, TokenValue<'_>)>) {
 
     assert_eq!(actual_tokens, tokens);
 }
+
+#[test]
+fn radix_integers() {
+    test_tokens_eq(
+        Lexer::new("0xFF 0x1f 0o755 0b1010 0b11i 0xFFl 0 0.5"),
+        vec![
+            (TokenKind::Integer, TokenValue::Integer(255)),
+            (TokenKind::Integer, TokenValue::Integer(31)),
+            (TokenKind::Integer, TokenValue::Integer(493)),
+            (TokenKind::Integer, TokenValue::Integer(10)),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(3, IntegerSuffix::Integer),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(255, IntegerSuffix::Long),
+            ),
+            (TokenKind::Integer, TokenValue::Integer(0)),
+            (TokenKind::Decimal, TokenValue::Decimal(0.5)),
+        ],
+    );
+}
+
+#[test]
+fn invalid_radix_integers() {
+    let errors = Lexer::new("0b1012 0o8 0xG 0xFFFFFFFFFFFFFFFFFF 0xFFFFFFFFi")
+        .map(|token| token.unwrap_err().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        [
+            "invalid binary number",
+            "invalid octal number",
+            "invalid hexadecimal number",
+            "integer literal out of range",
+            "integer literal out of range"
+        ]
+    );
+
+    let error = Lexer::new("0b1012").next().unwrap().unwrap_err();
+    assert_eq!(
+        error.help().unwrap().to_string(),
+        "binary numbers only use the digits 0 and 1"
+    );
+}
+
+#[test]
+fn scientific_notation() {
+    test_tokens_eq(
+        Lexer::new("1e10 6.022e23 2.5E-4 1e+2 5else"),
+        vec![
+            (TokenKind::Decimal, TokenValue::Decimal(1e10)),
+            (TokenKind::Decimal, TokenValue::Decimal(6.022e23)),
+            (TokenKind::Decimal, TokenValue::Decimal(2.5e-4)),
+            (TokenKind::Decimal, TokenValue::Decimal(100.0)),
+            (TokenKind::Integer, TokenValue::Integer(5)),
+            (TokenKind::Else, TokenValue::None),
+        ],
+    );
+
+    let errors = Lexer::new("1e 1e+ 1e5i")
+        .map(|token| token.unwrap_err().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        [
+            "missing exponent",
+            "missing exponent",
+            "invalid number suffix"
+        ]
+    );
+}
+
+#[test]
+fn raw_strings() {
+    test_tokens_eq(
+        Lexer::new(r####"r"C:\temp\x" r#"say "hi" ${x}"# r##"a "# b"## r"""####),
+        vec![
+            (TokenKind::String, TokenValue::String(r"C:\temp\x".into())),
+            (
+                TokenKind::String,
+                TokenValue::String(r#"say "hi" ${x}"#.into()),
+            ),
+            (TokenKind::String, TokenValue::String(r##"a "# b"##.into())),
+            (TokenKind::String, TokenValue::String("".into())),
+        ],
+    );
+
+    test_tokens_eq(
+        Lexer::new("r #r"),
+        vec![
+            (TokenKind::Identifier, identifier("r", 0)),
+            (TokenKind::Hash, TokenValue::None),
+            (TokenKind::Identifier, identifier("r", 3)),
+        ],
+    );
+
+    let error = Lexer::new(r##"r#"unterminated" "##)
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "unterminated raw string");
+}
+
+#[test]
+fn multiline_strings() {
+    let source =
+        "let a = \"\"\"\n        first\n          indented\\t\n\n        last\n        \"\"\";";
+    let tokens = Lexer::new(source)
+        .map(|token| token.unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(tokens[3].kind, TokenKind::String);
+    assert_eq!(
+        tokens[3].value,
+        TokenValue::String("first\n  indented\t\n\nlast".into())
+    );
+    assert_eq!(tokens[3].span, SourceSpan::new(8.into(), source.len() - 9));
+    assert_eq!(tokens[4].kind, TokenKind::Semicolon);
+
+    test_tokens_eq(
+        Lexer::new(r#""""single "line""""#),
+        vec![(
+            TokenKind::String,
+            TokenValue::String(r#"single "line"#.into()),
+        )],
+    );
+}
+
+#[test]
+fn invalid_multiline_strings() {
+    let error = Lexer::new("\"\"\"\n    text\n  less\n    \"\"\"")
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "insufficient indentation in multi-line string"
+    );
+
+    let error = Lexer::new("\"\"\"\n  never closed\n")
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "unterminated multi-line string");
+    assert_eq!(error.labels().unwrap().next().unwrap().offset(), 0);
+}
+
+#[test]
+fn empty_string_before_quote() {
+    test_tokens_eq(
+        Lexer::new(r#""""a""" "" "a""#),
+        vec![
+            (TokenKind::String, TokenValue::String("a".into())),
+            (TokenKind::String, TokenValue::String("".into())),
+            (TokenKind::String, TokenValue::String("a".into())),
+        ],
+    );
+
+    let error = Lexer::new(r#""""a""#).next().unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "unterminated multi-line string");
+}
+
+#[test]
+fn width_suffixes() {
+    test_tokens_eq(
+        Lexer::new("255u8 1000u16 7u32 18446744073709551615u64 127i8 32767i16 0xFFu8 128i8"),
+        vec![
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(255, IntegerSuffix::UInt8),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(1000, IntegerSuffix::UInt16),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(7, IntegerSuffix::UInt32),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(u64::MAX.into(), IntegerSuffix::UInt64),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(127, IntegerSuffix::Int8),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(32767, IntegerSuffix::Int16),
+            ),
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(255, IntegerSuffix::UInt8),
+            ),
+            // Fits once negated, which the parser checks
+            (
+                TokenKind::Integer,
+                TokenValue::SuffixedInteger(128, IntegerSuffix::Int8),
+            ),
+        ],
+    );
+
+    for (code, help) in [
+        ("256u8", "256 does not fit in an unsigned 8-bit integer"),
+        ("129i8", "129 does not fit in an 8-bit integer"),
+        (
+            "65536u16",
+            "65536 does not fit in an unsigned 16-bit integer",
+        ),
+        ("0x1FFu8", "0x1FF does not fit in an unsigned 8-bit integer"),
+    ] {
+        let error = Lexer::new(code).next().unwrap().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "integer literal out of range",
+            "{}",
+            code
+        );
+        assert_eq!(error.help().unwrap().to_string(), help, "{}", code);
+    }
+}
+
+#[test]
+fn comments() {
+    test_tokens_eq(
+        Lexer::new("let // a comment\na /* one */ = /* two /* nested */ */ 1 /* three /* b /* c */ */ */; 4 / 2"),
+        vec![
+            (TokenKind::Let, TokenValue::None),
+            (TokenKind::Identifier, identifier("a", 17)),
+            (TokenKind::Equal, TokenValue::None),
+            (TokenKind::Integer, TokenValue::Integer(1)),
+            (TokenKind::Semicolon, TokenValue::None),
+            (TokenKind::Integer, TokenValue::Integer(4)),
+            (TokenKind::Slash, TokenValue::None),
+            (TokenKind::Integer, TokenValue::Integer(2)),
+        ],
+    );
+}
+
+#[test]
+fn unterminated_block_comment() {
+    let mut lexer = Lexer::new("a /* outer /* inner */ still open");
+    lexer.next().unwrap().unwrap();
+
+    let error = lexer.next().unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "unterminated block comment");
+    assert_eq!(error.labels().unwrap().next().unwrap().offset(), 2);
+    assert!(lexer.next().is_none());
+}
+
+#[test]
+fn doc_comments() {
+    test_tokens_eq(
+        Lexer::new("/// Adds numbers.\r\n// plain\n//// plain\n/** Block\n docs */ /* plain */ /**/ /*** plain */ fn"),
+        vec![
+            (
+                TokenKind::DocComment,
+                TokenValue::DocComment(" Adds numbers.".into()),
+            ),
+            (
+                TokenKind::DocComment,
+                TokenValue::DocComment(" Block\n docs ".into()),
+            ),
+            (TokenKind::Function, TokenValue::None),
+        ],
+    );
+
+    test_tokens_eq(
+        Lexer::new("/// Adds numbers.\nfn /** Block */ main").skip_doc_comments(),
+        vec![
+            (TokenKind::Function, TokenValue::None),
+            (TokenKind::Identifier, identifier("main", 34)),
+        ],
+    );
+}
+
+#[test]
+fn tab_columns() {
+    let source = "fn main() {\n\tlet a = 1;\n  \tlet b = 2;\n}";
+
+    let columns = Lexer::new(source)
+        .map(Result::unwrap)
+        .filter(|token| token.kind == TokenKind::Let)
+        .map(|token| {
+            let offset = token.span.offset();
+            (
+                Position::from_offset_with_tabs(source, offset, DEFAULT_TAB_WIDTH),
+                Position::from_offset_with_tabs(source, offset, 4).column,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        columns,
+        vec![
+            (Position { line: 2, column: 9 }, 5),
+            (Position { line: 3, column: 9 }, 5),
+        ]
+    );
+}
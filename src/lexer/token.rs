@@ -28,10 +28,25 @@ pub enum TokenValue<'ast> {
     None,
     Boolean(bool),
     Integer(i64),
+    SuffixedInteger(i128, IntegerSuffix),
     Decimal(f64),
     String(Cow<'ast, str>),
+    InterpolatedString(Vec<StringPart<'ast>>),
     Character(char),
-    Identifier(Cow<'ast, str>),
+    /// The name of an identifier, along with the span it was written at.
+    Identifier(Cow<'ast, str>, miette::SourceSpan),
+    /// The text of a doc comment, without its `///` or `/**` and `*/`.
+    DocComment(Cow<'ast, str>),
+}
+
+/// A part of an interpolated string; `"x = ${x}"` is the fragment `x = ` followed by the
+/// expression `x`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringPart<'ast> {
+    /// The text of a literal fragment, along with the span of its source code.
+    Fragment(Cow<'ast, str>, miette::SourceSpan),
+    /// The source code of an embedded expression, between its `${` and `}`.
+    Expression(miette::SourceSpan),
 }
 
 impl Display for TokenValue<'_> {
@@ -40,10 +55,120 @@ impl Display for TokenValue<'_> {
             TokenValue::None => write!(f, ""),
             TokenValue::Boolean(value) => write!(f, "{}", value),
             TokenValue::Integer(value) => write!(f, "{}", value),
+            TokenValue::SuffixedInteger(value, suffix) => write!(f, "{}{}", value, suffix),
             TokenValue::Decimal(value) => write!(f, "{}", value),
             TokenValue::String(value) => write!(f, "{}", value),
+            TokenValue::InterpolatedString(parts) => {
+                for part in parts {
+                    match part {
+                        StringPart::Fragment(fragment, _) => write!(f, "{}", fragment)?,
+                        StringPart::Expression(_) => write!(f, "${{...}}")?,
+                    }
+                }
+
+                Ok(())
+            }
             TokenValue::Character(value) => write!(f, "{}", value),
-            TokenValue::Identifier(value) => write!(f, "{}", value),
+            TokenValue::Identifier(value, _) => write!(f, "{}", value),
+            TokenValue::DocComment(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// The suffix of an integer literal, fixing its width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerSuffix {
+    /// A 32-bit integer; `5i` or `5i32`.
+    Integer,
+    /// A 64-bit integer; `5l` or `5i64`.
+    Long,
+    /// An 8-bit integer; `5i8`.
+    Int8,
+    /// A 16-bit integer; `5i16`.
+    Int16,
+    /// An unsigned 8-bit integer; `5u8`.
+    UInt8,
+    /// An unsigned 16-bit integer; `5u16`.
+    UInt16,
+    /// An unsigned 32-bit integer; `5u32`.
+    UInt32,
+    /// An unsigned 64-bit integer; `5u64`.
+    UInt64,
+}
+
+impl IntegerSuffix {
+    /// The suffix as written after a number, such as the `u8` in `255u8`.
+    pub fn parse(suffix: &str) -> Option<Self> {
+        match suffix {
+            "i" | "i32" => Some(IntegerSuffix::Integer),
+            "l" | "i64" => Some(IntegerSuffix::Long),
+            "i8" => Some(IntegerSuffix::Int8),
+            "i16" => Some(IntegerSuffix::Int16),
+            "u8" => Some(IntegerSuffix::UInt8),
+            "u16" => Some(IntegerSuffix::UInt16),
+            "u32" => Some(IntegerSuffix::UInt32),
+            "u64" => Some(IntegerSuffix::UInt64),
+            _ => None,
+        }
+    }
+
+    /// The values an integer with this suffix can hold.
+    pub fn range(&self) -> std::ops::RangeInclusive<i128> {
+        match self {
+            IntegerSuffix::Integer => i32::MIN.into()..=i32::MAX.into(),
+            IntegerSuffix::Long => i64::MIN.into()..=i64::MAX.into(),
+            IntegerSuffix::Int8 => i8::MIN.into()..=i8::MAX.into(),
+            IntegerSuffix::Int16 => i16::MIN.into()..=i16::MAX.into(),
+            IntegerSuffix::UInt8 => 0..=u8::MAX.into(),
+            IntegerSuffix::UInt16 => 0..=u16::MAX.into(),
+            IntegerSuffix::UInt32 => 0..=u32::MAX.into(),
+            IntegerSuffix::UInt64 => 0..=u64::MAX.into(),
+        }
+    }
+
+    /// The error for an integer `text` with this suffix whose value does not fit its type.
+    pub fn out_of_range(&self, text: &str, span: miette::SourceSpan) -> miette::Report {
+        let help = match self {
+            IntegerSuffix::Integer => format!(
+                "{} does not fit in a 32-bit integer, use the `l` suffix for a 64-bit integer",
+                text
+            ),
+            suffix => format!("{} does not fit in {}", text, suffix.describe()),
+        };
+
+        miette::miette! {
+            labels = vec![miette::LabeledSpan::at(span, "this number")],
+            help = help,
+            "integer literal out of range"
+        }
+    }
+
+    /// The integer type this suffix gives, such as "an unsigned 8-bit integer".
+    pub fn describe(&self) -> &'static str {
+        match self {
+            IntegerSuffix::Integer => "a 32-bit integer",
+            IntegerSuffix::Long => "a 64-bit integer",
+            IntegerSuffix::Int8 => "an 8-bit integer",
+            IntegerSuffix::Int16 => "a 16-bit integer",
+            IntegerSuffix::UInt8 => "an unsigned 8-bit integer",
+            IntegerSuffix::UInt16 => "an unsigned 16-bit integer",
+            IntegerSuffix::UInt32 => "an unsigned 32-bit integer",
+            IntegerSuffix::UInt64 => "an unsigned 64-bit integer",
+        }
+    }
+}
+
+impl Display for IntegerSuffix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegerSuffix::Integer => write!(f, "i"),
+            IntegerSuffix::Long => write!(f, "l"),
+            IntegerSuffix::Int8 => write!(f, "i8"),
+            IntegerSuffix::Int16 => write!(f, "i16"),
+            IntegerSuffix::UInt8 => write!(f, "u8"),
+            IntegerSuffix::UInt16 => write!(f, "u16"),
+            IntegerSuffix::UInt32 => write!(f, "u32"),
+            IntegerSuffix::UInt64 => write!(f, "u64"),
         }
     }
 }
@@ -77,6 +202,8 @@ pub enum TokenKind {
     Dot,
     /// A colon; `:`.
     Colon,
+    /// A double colon, separating the segments of a path; `::`.
+    DoubleColon,
     /// A semicolon; `;`.
     Semicolon,
 
@@ -151,18 +278,23 @@ pub enum TokenKind {
 
     /// A boolean; `true`, `false`.
     Boolean,
-    /// A number; `42`, `12`, `-7`.
+    /// A number, optionally suffixed with its width; `42`, `12i`, `7l`.
     Integer,
     /// A decimal; `3.14`, `2.718`, `-1.0`.
     Decimal,
     /// A string; `"foo"`, `"bar"`, `"baz"`.
     String,
+    /// A string with embedded expressions; `"x = ${x}"`.
+    InterpolatedString,
     /// A character; `'a'`, `'b'`, `'c'`.
     Character,
 
     /// An identifying name; `foo`, `bar`, `baz`.
     Identifier,
 
+    /// A documentation comment; `/// foo`, `/** foo */`.
+    DocComment,
+
     /// A struct keyword; `struct`.
     Struct,
     /// A enum keyword; `enum`.
@@ -172,8 +304,10 @@ pub enum TokenKind {
 
     /// The boolean type; `bool`.
     BooleanType,
-    /// The integer type; `int`.
+    /// The 32-bit integer type; `int`, `i32`.
     IntegerType,
+    /// The 64-bit integer type; `i64`.
+    LongType,
     /// The decimal type; `dec`.
     DecimalType,
     /// The string type; `str`.
@@ -182,6 +316,32 @@ pub enum TokenKind {
     CharacterType,
 }
 
+impl TokenKind {
+    /// Whether this is a reserved word, which reads like a name but can't be used as one.
+    pub fn is_keyword(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::If
+                | TokenKind::Else
+                | TokenKind::While
+                | TokenKind::For
+                | TokenKind::Let
+                | TokenKind::Type
+                | TokenKind::Function
+                | TokenKind::Return
+                | TokenKind::Struct
+                | TokenKind::Enum
+                | TokenKind::Trait
+                | TokenKind::BooleanType
+                | TokenKind::IntegerType
+                | TokenKind::LongType
+                | TokenKind::DecimalType
+                | TokenKind::StringType
+                | TokenKind::CharacterType
+        )
+    }
+}
+
 impl Display for TokenKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -197,6 +357,7 @@ impl Display for TokenKind {
             TokenKind::Comma => write!(f, "`,`"),
             TokenKind::Dot => write!(f, "`.`"),
             TokenKind::Colon => write!(f, "`:`"),
+            TokenKind::DoubleColon => write!(f, "`::`"),
             TokenKind::Semicolon => write!(f, "`;`"),
             TokenKind::Plus => write!(f, "`+`"),
             TokenKind::Minus => write!(f, "`-`"),
@@ -222,8 +383,10 @@ impl Display for TokenKind {
             TokenKind::Integer => write!(f, "an integer value"),
             TokenKind::Decimal => write!(f, "a decimal value"),
             TokenKind::String => write!(f, "a string value"),
+            TokenKind::InterpolatedString => write!(f, "an interpolated string"),
             TokenKind::Character => write!(f, "a character value"),
             TokenKind::Identifier => write!(f, "an identifier"),
+            TokenKind::DocComment => write!(f, "a doc comment"),
             TokenKind::Struct => write!(f, "`struct`"),
             TokenKind::Enum => write!(f, "`enum`"),
             TokenKind::Percent => write!(f, "`%`"),
@@ -240,6 +403,7 @@ impl Display for TokenKind {
             TokenKind::Trait => write!(f, "`trait`"),
             TokenKind::BooleanType => write!(f, "a boolean type"),
             TokenKind::IntegerType => write!(f, "an integer type"),
+            TokenKind::LongType => write!(f, "a long integer type"),
             TokenKind::DecimalType => write!(f, "a decimal type"),
             TokenKind::StringType => write!(f, "a string type"),
             TokenKind::CharacterType => write!(f, "a character type"),
@@ -0,0 +1,90 @@
+use ast::{Expression, Module, TypedExpression};
+use lexer::Lexer;
+use miette::{Diagnostic, LabeledSpan, MietteDiagnostic};
+use parser::Parser;
+use typer::TypeChecker;
+
+pub mod ast;
+pub mod compiler;
+pub mod highlighter;
+pub mod lexer;
+pub mod parser;
+pub mod report;
+pub mod typer;
+
+#[cfg(test)]
+mod tests;
+
+pub type Result<T> = std::result::Result<T, Vec<miette::MietteDiagnostic>>;
+
+/// Parses `source` into a module, along with any diagnostics. The module is only returned when
+/// parsing succeeded.
+pub fn parse(source: &str) -> (Option<Module<'_, Expression<'_>>>, Vec<MietteDiagnostic>) {
+    let mut parser = Parser::new(Lexer::new(source));
+
+    match parser.parse() {
+        Ok(module) => (Some(module), vec![]),
+        Err(reports) => (
+            None,
+            reports
+                .iter()
+                .map(|report| to_diagnostic(report.as_ref()))
+                .collect(),
+        ),
+    }
+}
+
+/// Parses and type checks `source`, along with any diagnostics, warnings included. The typed
+/// modules are only returned when both stages succeeded.
+pub fn check(
+    source: &str,
+) -> (
+    Option<Vec<Module<'_, TypedExpression<'_>>>>,
+    Vec<MietteDiagnostic>,
+) {
+    let module = match parse(source) {
+        (Some(module), _) => module,
+        (None, diagnostics) => return (None, diagnostics),
+    };
+
+    let mut type_checker = TypeChecker::new();
+
+    match type_checker.type_check(vec![module]) {
+        Ok(modules) => (Some(modules), type_checker.warnings().to_vec()),
+        Err(mut diagnostics) => {
+            diagnostics.extend_from_slice(type_checker.warnings());
+            (None, diagnostics)
+        }
+    }
+}
+
+/// Converts a report into a plain diagnostic. Reports are wrapped in context such as `while
+/// parsing block`, so the message is taken from the innermost cause and the context is kept as
+/// notes after the help text, innermost first.
+fn to_diagnostic(diagnostic: &dyn Diagnostic) -> MietteDiagnostic {
+    let mut cause: &dyn std::error::Error = diagnostic;
+    let mut notes = vec![];
+    while let Some(source) = cause.source() {
+        notes.push(format!("note: {}", cause));
+        cause = source;
+    }
+    notes.reverse();
+
+    let help = diagnostic
+        .help()
+        .map(|help| help.to_string())
+        .into_iter()
+        .chain(notes)
+        .collect::<Vec<_>>();
+
+    MietteDiagnostic {
+        message: cause.to_string(),
+        code: diagnostic.code().map(|code| code.to_string()),
+        severity: diagnostic.severity(),
+        help: (!help.is_empty()).then(|| help.join("\n")),
+        url: diagnostic.url().map(|url| url.to_string()),
+        labels: diagnostic
+            .labels()
+            .map(|labels| labels.collect::<Vec<LabeledSpan>>()),
+    }
+}
@@ -1,17 +1,9 @@
-use crate::typer::TypeChecker;
-use highlighter::SomHighlighter;
-use lexer::Lexer;
 use miette::miette;
-use parser::Parser;
+use som::report::{self, ColorChoice};
+use som::typer::TypeChecker;
+use som::{ast, lexer::Lexer, parser::Parser, typer};
 use std::vec;
 
-pub mod ast;
-pub mod compiler;
-pub mod highlighter;
-pub mod lexer;
-pub mod parser;
-pub mod typer;
-
 const INPUT: &str = "
 
 fn main() {
@@ -19,18 +11,11 @@ fn main() {
 }
 ";
 
-pub type Result<T> = std::result::Result<T, Vec<miette::MietteDiagnostic>>;
-
 fn main() {
-    miette::set_hook(Box::new(|_| {
-        Box::new(
-            miette::MietteHandlerOpts::new()
-                .terminal_links(true)
-                .unicode(true)
-                .context_lines(2)
-                .with_syntax_highlighting(SomHighlighter {})
-                .build(),
-        )
+    let color = ColorChoice::from_args(std::env::args());
+    let tab_width = report::tab_width_from_args(std::env::args());
+    miette::set_hook(Box::new(move |_| {
+        Box::new(report::handler(color, tab_width))
     }))
     .unwrap();
 
@@ -41,19 +26,27 @@ fn main() {
     let mut parser = Parser::new(lexer);
     let module: ast::Module<'_, ast::Expression<'_>> = match parser.parse() {
         Ok(statements) => statements,
-        Err(err) => {
-            println!("{:?}", err.with_source_code(INPUT));
+        Err(errors) => {
+            for error in errors {
+                println!("{:?}", error.with_source_code(INPUT));
+            }
             return;
         }
     };
 
     let mut typechecker = TypeChecker::new();
     match typechecker.type_check(vec![module]) {
-        Ok(_) => {}
+        Ok(modules) => {
+            if let Err(err) = typer::entry_point(&modules) {
+                errors.extend(err);
+            }
+        }
         Err(err) => errors.extend(err),
     }
 
-    for error in errors {
+    errors.extend_from_slice(typechecker.warnings());
+
+    for error in report::deduplicate(errors) {
         println!("{:?}", miette!(error).with_source_code(INPUT));
     }
 }
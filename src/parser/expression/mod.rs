@@ -2,7 +2,10 @@ use miette::{Result, SourceSpan};
 
 use super::Parser;
 use crate::{
-    ast::{CombineSpan, Expression, ExpressionValue, Lambda, ParameterDeclaration, Spannable},
+    ast::{
+        CombineSpan, Expression, ExpressionValue, Identifier, Lambda, ParameterDeclaration, Path,
+        Primitive, Spannable,
+    },
     lexer::{TokenKind, TokenValue},
     parser::lookup::BindingPower,
 };
@@ -14,10 +17,34 @@ pub mod unary;
 pub fn parse<'ast>(
     parser: &mut Parser<'ast>,
     binding_power: BindingPower,
+) -> Result<Expression<'ast>> {
+    if parser.depth >= parser.max_depth {
+        let labels = match parser.lexer.peek() {
+            Some(Ok(token)) => vec![token.label("nested too deeply")],
+            _ => vec![],
+        };
+
+        return Err(miette::miette! {
+            labels = labels,
+            help = format!("expressions can be nested at most {} levels deep", parser.max_depth),
+            "expression nesting too deep"
+        });
+    }
+
+    parser.depth += 1;
+    let expression = parse_nested(parser, binding_power);
+    parser.depth -= 1;
+
+    expression
+}
+
+fn parse_nested<'ast>(
+    parser: &mut Parser<'ast>,
+    binding_power: BindingPower,
 ) -> Result<Expression<'ast>> {
     let token = match parser.lexer.peek().as_ref() {
         Some(Ok(token)) => token,
-        Some(Err(err)) => return Err(miette::miette!(err.to_string())), // FIXME: better error handling
+        Some(Err(_)) => return Err(parser.lexer_error()),
         None => {
             return Err(miette::miette! {
                 help = "expected an expression",
@@ -42,7 +69,7 @@ pub fn parse<'ast>(
     while let Some(token) = next_token {
         let token = match token {
             Ok(token) => token,
-            Err(err) => return Err(miette::miette!(err.to_string())), // FIXME: better error handling
+            Err(_) => return Err(parser.lexer_error()),
         };
 
         let token_binding_power = {
@@ -53,7 +80,9 @@ pub fn parse<'ast>(
                 .clone()
         };
 
-        if binding_power > token_binding_power {
+        // Operators of the same binding power are left-associative, so `a - b - c` is
+        // `(a - b) - c`
+        if binding_power >= token_binding_power {
             break;
         }
 
@@ -72,6 +101,43 @@ pub fn parse<'ast>(
     Ok(lhs)
 }
 
+pub fn path<'ast>(
+    parser: &mut Parser<'ast>,
+    lhs: Expression<'ast>,
+    _binding_power: BindingPower,
+) -> Result<Expression<'ast>> {
+    let mut segments = match lhs.value {
+        ExpressionValue::Primitive(Primitive::Identifier(name)) => vec![Identifier {
+            name,
+            span: lhs.span,
+        }],
+        ExpressionValue::Path(path) => path.segments,
+        _ => {
+            return Err(miette::miette! {
+                labels = vec![lhs.label("expected a name")],
+                help = "only names can be joined into a path",
+                "expected a name before `::`"
+            })
+        }
+    };
+
+    let token = parser
+        .lexer
+        .expect(TokenKind::Identifier, "expected an identifier")?;
+
+    segments.push(match token.value {
+        TokenValue::Identifier(name, span) => Identifier { name, span },
+        _ => unreachable!(),
+    });
+
+    let span = SourceSpan::combine(vec![lhs.span, token.span]);
+
+    Ok(Expression::at(
+        span,
+        ExpressionValue::Path(Path { segments, span }),
+    ))
+}
+
 pub fn call<'ast>(
     parser: &mut Parser<'ast>,
     lhs: Expression<'ast>,
@@ -88,6 +154,9 @@ pub fn call<'ast>(
             parser
                 .lexer
                 .expect(TokenKind::Comma, "expected a comma between arguments")?;
+            if parser.lexer.peek_expect(TokenKind::ParenClose).is_some() {
+                break;
+            }
         }
 
         let argument = parse(parser, BindingPower::None)?;
@@ -123,6 +192,9 @@ pub fn lambda<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
             parser
                 .lexer
                 .expect(TokenKind::Comma, "expected a comma between arguments")?;
+            if parser.lexer.peek_expect(TokenKind::Pipe).is_some() {
+                break;
+            }
         }
 
         let parameter = parser.lexer.expect(
@@ -131,7 +203,7 @@ pub fn lambda<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
         )?;
 
         let name = match parameter.value {
-            TokenValue::Identifier(v) => v,
+            TokenValue::Identifier(v, _) => v,
             _ => unreachable!(),
         };
 
@@ -152,12 +224,21 @@ pub fn lambda<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
         .lexer
         .expect(TokenKind::Pipe, "expected a pipe after lambda arguments")?;
 
+    let explicit_return_type = match parser.lexer.peek_expect(TokenKind::Arrow) {
+        None => None,
+        Some(_) => {
+            parser.lexer.next();
+            Some(super::typing::parse(parser, BindingPower::None)?)
+        }
+    };
+
     let body = parse(parser, BindingPower::None)?;
 
     Ok(Expression::at_multiple(
         vec![pipe.span, body.span],
         ExpressionValue::Lambda(Lambda {
             parameters,
+            explicit_return_type,
             body: Box::new(body),
         }),
     ))
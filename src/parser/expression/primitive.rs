@@ -1,9 +1,9 @@
 use crate::{
     ast::{Expression, ExpressionValue, Primitive, Spannable},
-    lexer::{TokenKind, TokenValue},
-    parser::Parser,
+    lexer::{IntegerSuffix, StringPart, Token, TokenKind, TokenValue},
+    parser::{lookup::BindingPower, Parser},
 };
-use miette::Result;
+use miette::{LabeledSpan, Result, SourceSpan};
 
 pub fn integer<'ast>(parser: &mut Parser) -> Result<Expression<'ast>> {
     let token = parser
@@ -11,16 +11,49 @@ pub fn integer<'ast>(parser: &mut Parser) -> Result<Expression<'ast>> {
         .expect(TokenKind::Integer, "expected an integer")?;
 
     let value = match token.value {
-        TokenValue::Integer(v) => v,
+        TokenValue::Integer(v) => Primitive::Integer(v),
+        TokenValue::SuffixedInteger(v, suffix) => {
+            suffixed_integer(v, suffix, digits(token.original), token.span)?
+        }
         _ => unreachable!(),
     };
 
     Ok(Expression::at(
         token.span,
-        ExpressionValue::Primitive(Primitive::Integer(value)),
+        ExpressionValue::Primitive(value),
     ))
 }
 
+/// The digits of an integer without its suffix, such as the `0xFF` of `0xFFu8`. No digit is an
+/// `i`, `l` or `u`, so the suffix starts at the last of them.
+pub fn digits(integer: &str) -> &str {
+    &integer[..integer.rfind(['i', 'l', 'u']).unwrap_or(integer.len())]
+}
+
+/// The value of an integer with a suffix, which may be negative when a minus is folded into it.
+/// The value is range checked here, as the lexer lets through values that only fit once negated,
+/// like the `128` of `-128i8`.
+pub fn suffixed_integer<'ast>(
+    value: i128,
+    suffix: IntegerSuffix,
+    text: &str,
+    span: SourceSpan,
+) -> Result<Primitive<'ast>> {
+    if !suffix.range().contains(&value) {
+        return Err(suffix.out_of_range(text, span));
+    }
+
+    match suffix {
+        IntegerSuffix::Integer => Ok(Primitive::Int32(value as i64)),
+        IntegerSuffix::Long => Ok(Primitive::Int64(value as i64)),
+        suffix => Err(miette::miette! {
+            labels = vec![LabeledSpan::at(span, suffix.describe())],
+            help = format!("{} integers are not supported yet, use `i` or `l`", suffix),
+            "feature not yet implemented"
+        }),
+    }
+}
+
 pub fn decimal<'ast>(parser: &mut Parser) -> Result<Expression<'ast>> {
     let token = parser
         .lexer
@@ -69,18 +102,59 @@ pub fn character<'ast>(parser: &mut Parser) -> Result<Expression<'ast>> {
     ))
 }
 
+/// Parses a string, joining adjacent string literals such as `"foo" "bar"` into one. When one
+/// of them is interpolated, as in `"foo" "${bar}"`, the joined string is interpolated too.
 pub fn string<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
-    let token = parser
-        .lexer
-        .expect(TokenKind::String, "expected a string")?;
-
-    let value = match token.value {
-        TokenValue::String(v) => v,
-        _ => unreachable!(),
+    let is_string = |token: &Token| {
+        matches!(
+            token.kind,
+            TokenKind::String | TokenKind::InterpolatedString
+        )
     };
 
-    Ok(Expression::at(
-        token.span,
+    let mut parts = vec![];
+    let mut spans = vec![];
+    let mut interpolated = false;
+
+    loop {
+        let token = parser.lexer.expect_where(is_string, "expected a string")?;
+        spans.push(token.span);
+
+        match token.value {
+            TokenValue::String(value) => parts.push(Expression::at(
+                token.span,
+                ExpressionValue::Primitive(Primitive::String(value)),
+            )),
+            TokenValue::InterpolatedString(string_parts) => {
+                interpolated = true;
+                parts.extend(embedded_parts(parser, string_parts)?);
+            }
+            _ => unreachable!(),
+        }
+
+        if !matches!(parser.lexer.peek(), Some(Ok(token)) if is_string(token)) {
+            break;
+        }
+    }
+
+    if interpolated {
+        return Ok(Expression::at_multiple(
+            spans,
+            ExpressionValue::Interpolation(parts),
+        ));
+    }
+
+    let mut parts = parts.into_iter().map(|part| match part.value {
+        ExpressionValue::Primitive(Primitive::String(value)) => value,
+        _ => unreachable!(),
+    });
+    let mut value = parts.next().unwrap_or_default();
+    for next in parts {
+        value.to_mut().push_str(&next);
+    }
+
+    Ok(Expression::at_multiple(
+        spans,
         ExpressionValue::Primitive(Primitive::String(value)),
     ))
 }
@@ -91,7 +165,7 @@ pub fn identifier<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
         .expect(TokenKind::Identifier, "expected an identifier")?;
 
     let value = match token.value {
-        TokenValue::Identifier(v) => v,
+        TokenValue::Identifier(v, _) => v,
         _ => unreachable!(),
     };
 
@@ -100,3 +174,41 @@ pub fn identifier<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
         ExpressionValue::Primitive(Primitive::Identifier(value)),
     ))
 }
+
+/// Parses the parts of an interpolated string such as `"x = ${x}"`, where every embedded
+/// expression is parsed from its own part of the source.
+fn embedded_parts<'ast>(
+    parser: &mut Parser<'ast>,
+    parts: Vec<StringPart<'ast>>,
+) -> Result<Vec<Expression<'ast>>> {
+    let mut expressions = vec![];
+
+    for part in parts {
+        match part {
+            StringPart::Fragment(fragment, span) => expressions.push(Expression::at(
+                span,
+                ExpressionValue::Primitive(Primitive::String(fragment)),
+            )),
+            StringPart::Expression(span) => {
+                let mut embedded = Parser::new(parser.lexer.slice(span));
+                embedded.depth = parser.depth;
+
+                let expression = super::parse(&mut embedded, BindingPower::None);
+                parser.errors.extend(std::mem::take(&mut embedded.errors));
+                let expression = expression?;
+
+                if let Some(Ok(token)) = embedded.lexer.peek() {
+                    return Err(miette::miette! {
+                        labels = vec![token.label("expected `}` here")],
+                        help = "only a single expression can be embedded in a string",
+                        "expected `}}` after the embedded expression, found {}", token.kind
+                    });
+                }
+
+                expressions.push(expression);
+            }
+        }
+    }
+
+    Ok(expressions)
+}
@@ -1,15 +1,16 @@
+use super::primitive;
 use crate::{
-    ast::{Expression, ExpressionValue, Spannable, UnaryOperator},
-    lexer::TokenKind,
+    ast::{CombineSpan, Expression, ExpressionValue, Spannable, UnaryOperator},
+    lexer::{Token, TokenKind, TokenValue},
     parser::{lookup::BindingPower, Parser},
 };
-use miette::Result;
+use miette::{Result, SourceSpan};
 
 pub fn negate<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
     let token = parser
         .lexer
         .expect(TokenKind::Not, "expected a negate operator")?;
-    let expression = crate::parser::expression::parse(parser, BindingPower::None)?;
+    let expression = crate::parser::expression::parse(parser, BindingPower::Unary)?;
 
     Ok(Expression::at_multiple(
         vec![token.span, expression.span],
@@ -24,7 +25,28 @@ pub fn negative<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
     let token = parser
         .lexer
         .expect(TokenKind::Minus, "expected a negative operator")?;
-    let expression = crate::parser::expression::parse(parser, BindingPower::None)?;
+
+    // The minus is part of a suffixed integer, so `-128i8` is range checked as -128
+    if let Some(Ok(Token {
+        value: TokenValue::SuffixedInteger(..),
+        ..
+    })) = parser.lexer.peek()
+    {
+        let literal = parser
+            .lexer
+            .expect(TokenKind::Integer, "expected an integer")?;
+        let span = SourceSpan::combine(vec![token.span, literal.span]);
+
+        let TokenValue::SuffixedInteger(value, suffix) = literal.value else {
+            unreachable!()
+        };
+        let text = format!("-{}", primitive::digits(literal.original));
+        let value = primitive::suffixed_integer(-value, suffix, &text, span)?;
+
+        return Ok(Expression::at(span, ExpressionValue::Primitive(value)));
+    }
+
+    let expression = crate::parser::expression::parse(parser, BindingPower::Unary)?;
 
     Ok(Expression::at_multiple(
         vec![token.span, expression.span],
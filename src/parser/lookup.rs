@@ -3,7 +3,7 @@ use crate::{
     ast::{Expression, ExpressionValue, Primitive, Spannable, Statement, StatementValue, Type},
     lexer::TokenKind,
 };
-use miette::{Context, Result, SourceSpan};
+use miette::{LabeledSpan, Result};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
@@ -21,6 +21,25 @@ pub enum BindingPower {
     Primary = 10,
 }
 
+impl BindingPower {
+    /// The binding power just below this one. Parsing the right side of an operator with it
+    /// makes the operator right-associative, so `a if b else c if d else e` nests to the right.
+    pub fn lower(&self) -> BindingPower {
+        match self {
+            BindingPower::None | BindingPower::Comma => BindingPower::None,
+            BindingPower::Assignment => BindingPower::Comma,
+            BindingPower::Logical => BindingPower::Assignment,
+            BindingPower::Relational => BindingPower::Logical,
+            BindingPower::Additive => BindingPower::Relational,
+            BindingPower::Multiplicative => BindingPower::Additive,
+            BindingPower::Unary => BindingPower::Multiplicative,
+            BindingPower::Call => BindingPower::Unary,
+            BindingPower::Member => BindingPower::Call,
+            BindingPower::Primary => BindingPower::Member,
+        }
+    }
+}
+
 pub type TypeHandler<'ast> = fn(&mut Parser<'ast>) -> Result<Type<'ast>>;
 pub type LeftTypeHandler<'ast> = fn(&mut Parser<'ast>, Type, BindingPower) -> Result<Type<'ast>>;
 pub type StatementHandler<'ast> =
@@ -119,10 +138,16 @@ impl Default for Lookup<'_> {
         .add_expression_handler(TokenKind::Boolean, expression::primitive::boolean)
         .add_expression_handler(TokenKind::Character, expression::primitive::character)
         .add_expression_handler(TokenKind::String, expression::primitive::string)
+        .add_expression_handler(TokenKind::InterpolatedString, expression::primitive::string)
         .add_expression_handler(TokenKind::Identifier, expression::primitive::identifier)
         .add_expression_handler(TokenKind::ParenOpen, group)
         .add_left_expression_handler(TokenKind::If, BindingPower::Logical, conditional)
         .add_left_expression_handler(TokenKind::ParenOpen, BindingPower::Call, expression::call)
+        .add_left_expression_handler(
+            TokenKind::DoubleColon,
+            BindingPower::Member,
+            expression::path,
+        )
         .add_expression_handler(TokenKind::Not, expression::unary::negate)
         .add_expression_handler(TokenKind::Minus, expression::unary::negative)
         .add_expression_handler(TokenKind::CurlyOpen, block)
@@ -195,6 +220,7 @@ impl Default for Lookup<'_> {
         .add_statement_handler(TokenKind::Let, statement::parse_declaration)
         .add_statement_handler(TokenKind::Type, statement::parse_type)
         .add_statement_handler(TokenKind::Struct, statement::parse_struct)
+        .add_statement_handler(TokenKind::Hash, statement::parse_attributed)
         .add_statement_handler(TokenKind::Enum, statement::parse_enum)
         .add_statement_handler(TokenKind::Function, statement::parse_function)
         .add_statement_handler(TokenKind::Trait, statement::parse_trait)
@@ -203,6 +229,7 @@ impl Default for Lookup<'_> {
         .add_type_handler(TokenKind::CharacterType, typing::parse_character)
         .add_type_handler(TokenKind::BooleanType, typing::parse_boolean)
         .add_type_handler(TokenKind::IntegerType, typing::parse_integer)
+        .add_type_handler(TokenKind::LongType, typing::parse_long)
         .add_type_handler(TokenKind::DecimalType, typing::parse_decimal)
         .add_type_handler(TokenKind::StringType, typing::parse_string)
         .add_type_handler(TokenKind::SquareOpen, typing::parse_collection)
@@ -224,7 +251,7 @@ fn conditional<'ast>(
         .lexer
         .expect(TokenKind::Else, "expected an else branch")?;
 
-    let falsy = expression::parse(parser, binding_power)?;
+    let falsy = expression::parse(parser, binding_power.lower())?;
 
     Ok(Expression::at_multiple(
         vec![condition.span, token.span, falsy.span],
@@ -259,8 +286,15 @@ fn block<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
     // A list of statements separated by semicolons. If the last statement is not ended with a semicolon, it is considered the return value.
     let mut statements = Vec::new();
     let mut last_is_return = true;
+    let mut recovered = false;
+    let mut statement_end = None;
 
     loop {
+        // Stop at the end of the input, so the missing closing curly brace is reported below.
+        if parser.lexer.peek().is_none() {
+            break;
+        }
+
         // Check if a closing curly brace is found.
         if parser.lexer.peek().is_some_and(|token| {
             token
@@ -270,14 +304,27 @@ fn block<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
             break;
         }
 
-        // Expect a semicolon after each statement except the last one.
-        if !statements.is_empty() {
-            parser
-                .lexer
-                .expect(TokenKind::Semicolon, "expected a semicolon")?;
+        // Expect a semicolon after each statement except the last one. A missing one is reported
+        // at the end of the statement before it, and parsing carries on as if it was there.
+        if let Some(end) = statement_end.filter(|_| !recovered) {
+            match parser.lexer.peek() {
+                Some(Ok(token)) if token.kind != TokenKind::Semicolon => {
+                    parser.errors.push(miette::miette! {
+                        labels = vec![LabeledSpan::at_offset(end, "add `;` here")],
+                        help = "statements in a block are separated by semicolons",
+                        "missing semicolon"
+                    });
+                }
+                _ => {
+                    parser
+                        .lexer
+                        .expect(TokenKind::Semicolon, "expected a semicolon")?;
+                }
+            }
         }
+        recovered = false;
 
-        if parser.lexer.peek().is_some_and(|token| {
+        if parser.lexer.peek().is_none_or(|token| {
             token
                 .as_ref()
                 .is_ok_and(|token| token.kind == TokenKind::CurlyClose)
@@ -286,31 +333,50 @@ fn block<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
             break;
         }
 
-        let statement =
-            crate::parser::statement::parse(parser, true).wrap_err("while parsing block")?;
-        statements.push(statement);
+        let start = parser.next_offset();
+
+        match crate::parser::statement::parse(parser, true) {
+            Ok(statement) => {
+                statements.push(statement);
+                statement_end = Some(parser.lexer.offset());
+                last_is_return = true;
+            }
+            Err(error) => {
+                // Skip the broken statement and carry on with the rest of the block
+                parser.recover(error.wrap_err("while parsing block"), start);
+
+                if parser.peek_is(TokenKind::Semicolon) {
+                    parser.lexer.next();
+                }
+
+                last_is_return = false;
+                recovered = true;
+            }
+        }
     }
 
-    let return_value = if last_is_return {
-        match statements.last().map(|s| &s.value) {
-            Some(StatementValue::Expression(_)) => match statements.pop().map(|s| s.value) {
-                Some(StatementValue::Expression(expression)) => expression,
-                _ => unreachable!(),
-            },
-            _ => Expression::at(
-                statements
-                    .last()
-                    .map_or(SourceSpan::new(0.into(), 0), |s| s.span),
-                ExpressionValue::Primitive(Primitive::Unit),
-            ),
+    let close = parser
+        .lexer
+        .expect(TokenKind::CurlyClose, "expected a right curly brace")?;
+
+    // A trailing expression is the value of the block. Without one, including when the block is
+    // empty, the block evaluates to unit, located at the closing curly brace.
+    let has_return_value = last_is_return
+        && matches!(
+            statements.last().map(|s| &s.value),
+            Some(StatementValue::Expression(_))
+        );
+
+    let return_value = match statements.pop() {
+        Some(Statement {
+            value: StatementValue::Expression(expression),
+            ..
+        }) if has_return_value => expression,
+        Some(statement) => {
+            statements.push(statement);
+            Expression::at(close.span, ExpressionValue::Primitive(Primitive::Unit))
         }
-    } else {
-        Expression::at(
-            statements
-                .last()
-                .map_or(SourceSpan::new(0.into(), 0), |s| s.span),
-            ExpressionValue::Primitive(Primitive::Unit),
-        )
+        None => Expression::at(close.span, ExpressionValue::Primitive(Primitive::Unit)),
     };
 
     let expression = Expression::at(
@@ -321,10 +387,6 @@ fn block<'ast>(parser: &mut Parser<'ast>) -> Result<Expression<'ast>> {
         },
     );
 
-    let close = parser
-        .lexer
-        .expect(TokenKind::CurlyClose, "expected a right curly brace")?;
-
     Ok(Expression::at_multiple(
         vec![open.span, close.span],
         ExpressionValue::Group(Box::new(expression)),
@@ -2,39 +2,151 @@ use std::borrow::Cow;
 
 use crate::{
     ast::{Expression, Module},
-    lexer::Lexer,
+    lexer::{Lexer, TokenKind},
 };
 use lookup::Lookup;
-use miette::Result;
+use miette::{LabeledSpan, Report};
 
 pub mod expression;
 pub mod lookup;
 pub mod statement;
+#[cfg(test)]
+mod tests;
 pub mod typing;
 
+/// How deeply expressions may be nested before parsing gives up, so that pathological input
+/// is reported instead of overflowing the stack.
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
 pub struct Parser<'ast> {
     lexer: Lexer<'ast>,
     lookup: Lookup<'ast>,
+    errors: Vec<Report>,
+    depth: usize,
+    max_depth: usize,
 }
 
 impl<'ast> Parser<'ast> {
+    /// Doc comments are skipped, as they are not attached to what they document yet.
     pub fn new(lexer: Lexer<'ast>) -> Self {
         Self {
-            lexer,
+            lexer: lexer.skip_doc_comments(),
             lookup: Lookup::default(),
+            errors: vec![],
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
 
-    pub fn parse(&mut self) -> Result<Module<'ast, Expression<'ast>>> {
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Parses the whole module. A statement that fails to parse is skipped, so the statements
+    /// after it are still parsed and every error is reported.
+    pub fn parse(&mut self) -> Result<Module<'ast, Expression<'ast>>, Vec<Report>> {
         let mut module = Module {
             name: Cow::Borrowed("main"),
             definitions: vec![],
         };
 
+        if self.lexer.peek().is_none() {
+            return Err(vec![miette::miette! {
+                labels = vec![LabeledSpan::at_offset(0, "no code found")],
+                help = "add a function, such as `fn main() {}`",
+                "empty program"
+            }]);
+        }
+
         while self.lexer.peek().is_some() {
-            module.definitions.push(statement::parse(self, false)?);
+            let start = self.next_offset();
+
+            match statement::parse(self, false) {
+                Ok(statement) => module.definitions.push(statement),
+                Err(error) => {
+                    self.recover(error, start);
+
+                    // A stray semicolon or closing brace cannot start a statement
+                    if self.peek_is(TokenKind::Semicolon) || self.peek_is(TokenKind::CurlyClose) {
+                        self.lexer.next();
+                    }
+                }
+            }
         }
 
-        Ok(module)
+        if self.errors.is_empty() {
+            Ok(module)
+        } else {
+            Err(std::mem::take(&mut self.errors))
+        }
+    }
+
+    /// Records a statement that failed to parse and skips ahead to where the next statement
+    /// likely starts. `start` is where the statement began, so at least one token is skipped.
+    pub(crate) fn recover(&mut self, error: Report, start: Option<usize>) {
+        self.errors.push(error);
+
+        if self.next_offset() == start {
+            self.lexer.next();
+        }
+
+        self.synchronize();
+    }
+
+    /// Skips tokens until a `;`, a closing `}` or a keyword that starts a statement, without
+    /// consuming it. Braces opened along the way are skipped as a whole.
+    pub(crate) fn synchronize(&mut self) {
+        let mut depth = 0;
+
+        while let Some(token) = self.lexer.peek() {
+            let kind = match token {
+                Ok(token) => token.kind.clone(),
+                Err(_) => {
+                    self.lexer.next();
+                    continue;
+                }
+            };
+
+            match kind {
+                TokenKind::CurlyOpen => depth += 1,
+                TokenKind::CurlyClose if depth == 0 => return,
+                TokenKind::CurlyClose => depth -= 1,
+                TokenKind::Semicolon
+                | TokenKind::Let
+                | TokenKind::Function
+                | TokenKind::Type
+                | TokenKind::Struct
+                | TokenKind::Enum
+                | TokenKind::Trait
+                | TokenKind::Return
+                    if depth == 0 =>
+                {
+                    return
+                }
+                _ => {}
+            }
+
+            self.lexer.next();
+        }
+    }
+
+    /// Takes the error of the next token, which failed to lex, so it is reported as is.
+    pub(crate) fn lexer_error(&mut self) -> Report {
+        match self.lexer.next() {
+            Some(Err(error)) => error,
+            _ => unreachable!("the next token should be an error"),
+        }
+    }
+
+    pub(crate) fn next_offset(&mut self) -> Option<usize> {
+        match self.lexer.peek() {
+            Some(Ok(token)) => Some(token.span.offset()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn peek_is(&mut self, kind: TokenKind) -> bool {
+        matches!(self.lexer.peek(), Some(Ok(token)) if token.kind == kind)
     }
 }
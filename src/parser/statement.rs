@@ -1,12 +1,13 @@
 use super::{expression, lookup::BindingPower, statement, typing, Parser};
 use crate::{
     ast::{
-        CombineSpan, EnumMemberDeclaration, Expression, FunctionHeader, ParameterDeclaration,
-        Spannable, Statement, StatementValue, StructMemberDeclaration,
+        Attribute, CombineSpan, EnumMemberDeclaration, Expression, ExpressionValue, FunctionHeader,
+        Identifier, ParameterDeclaration, Primitive, Spannable, Statement, StatementValue,
+        StructMemberDeclaration,
     },
     lexer::{TokenKind, TokenValue},
 };
-use miette::{Context, Result, SourceSpan};
+use miette::{Context, LabeledSpan, Result, SourceSpan};
 
 pub fn parse<'ast>(
     parser: &mut Parser<'ast>,
@@ -14,7 +15,7 @@ pub fn parse<'ast>(
 ) -> Result<Statement<'ast, Expression<'ast>>> {
     let token = match parser.lexer.peek().as_ref() {
         Some(Ok(token)) => token,
-        Some(Err(err)) => return Err(miette::miette!(err.to_string())), // FIXME: better error handling
+        Some(Err(_)) => return Err(parser.lexer_error()),
         None => {
             return Err(miette::miette! {
                 help = "expected a statement",
@@ -32,6 +33,7 @@ pub fn parse<'ast>(
         None => {
             let expression = expression::parse(parser, BindingPower::None)
                 .wrap_err("while parsing a statement")?;
+            suggest_keyword(parser, &expression);
 
             if !optional_semicolon {
                 let token = parser
@@ -55,6 +57,57 @@ pub fn parse<'ast>(
     Ok(statement)
 }
 
+/// The keywords a statement can start with, suggested when a statement that fails to parse
+/// starts with a name that is one typo away from one of them, like `lets`.
+const STATEMENT_KEYWORDS: [&str; 8] = [
+    "let", "type", "struct", "enum", "fn", "trait", "return", "if",
+];
+
+/// Suggests a keyword when a statement is a lone name that is one typo away from a keyword and
+/// is followed by more code, like `lets x = 1;`, which would otherwise only be reported as a
+/// missing semicolon.
+fn suggest_keyword(parser: &mut Parser, expression: &Expression) {
+    let name = match &expression.value {
+        ExpressionValue::Primitive(Primitive::Identifier(name)) if name.chars().count() > 1 => name,
+        _ => return,
+    };
+
+    let keyword = STATEMENT_KEYWORDS
+        .into_iter()
+        .find(|keyword| edit_distance(name, keyword) == 1);
+
+    let followed_by_code = parser.lexer.peek().is_some_and(|token| {
+        token.as_ref().is_ok_and(|token| {
+            token.kind != TokenKind::Semicolon && token.kind != TokenKind::CurlyClose
+        })
+    });
+
+    if let Some(keyword) = keyword.filter(|_| followed_by_code) {
+        parser.errors.push(miette::miette! {
+            labels = vec![expression.label(format!("did you mean `{}`?", keyword))],
+            help = format!("did you mean `{}`?", keyword),
+            "unknown keyword `{}`", name
+        });
+    }
+}
+
+/// The number of characters to insert, remove or replace to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(a != *b);
+            current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
 pub fn parse_declaration<'ast>(
     parser: &mut Parser<'ast>,
 ) -> Result<Statement<'ast, Expression<'ast>>> {
@@ -65,9 +118,18 @@ pub fn parse_declaration<'ast>(
         .lexer
         .expect(TokenKind::Identifier, "expected a variable name")?;
     let name = match identifier.value {
-        TokenValue::Identifier(identifier) => identifier,
+        TokenValue::Identifier(identifier, _) => identifier,
         _ => unreachable!(),
     };
+
+    let explicit_type = match parser.lexer.peek_expect(TokenKind::Tilde) {
+        None => None,
+        Some(_) => {
+            parser.lexer.next();
+            Some(typing::parse(parser, BindingPower::None)?)
+        }
+    };
+
     parser
         .lexer
         .expect(TokenKind::Equal, "expected an equal sign")?;
@@ -77,6 +139,7 @@ pub fn parse_declaration<'ast>(
         vec![token.span, identifier.span],
         StatementValue::Assignment {
             name,
+            explicit_type,
             value: expression,
         },
     ))
@@ -92,10 +155,12 @@ pub fn parse_struct<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, E
         .expect(TokenKind::Identifier, "expected a struct name")?;
 
     let name = match identifier.value {
-        TokenValue::Identifier(identifier) => identifier,
+        TokenValue::Identifier(identifier, _) => identifier,
         _ => unreachable!(),
     };
 
+    let type_parameters = parse_type_parameters(parser)?;
+
     parser.lexer.expect(TokenKind::Colon, "expected a colon")?;
 
     let mut fields = vec![];
@@ -109,6 +174,9 @@ pub fn parse_struct<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, E
             parser
                 .lexer
                 .expect(TokenKind::Comma, "expected a comma between fields")?;
+            if parser.lexer.peek_expect(TokenKind::Semicolon).is_some() {
+                break;
+            }
         }
 
         let field = parser
@@ -116,7 +184,7 @@ pub fn parse_struct<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, E
             .expect(TokenKind::Identifier, "expected a field name")?;
 
         let field_name = match field.value {
-            TokenValue::Identifier(field) => field,
+            TokenValue::Identifier(field, _) => field,
             _ => unreachable!(),
         };
 
@@ -137,10 +205,120 @@ pub fn parse_struct<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, E
 
     Ok(Statement::at_multiple(
         vec![token.span, identifier.span],
-        StatementValue::Struct { name, fields },
+        StatementValue::Struct {
+            attributes: vec![],
+            name,
+            type_parameters,
+            fields,
+        },
     ))
 }
 
+/// Parses a declaration preceded by attributes, such as `#[test] fn adds() { ... }`. Only
+/// functions and structs can have attributes.
+pub fn parse_attributed<'ast>(
+    parser: &mut Parser<'ast>,
+) -> Result<Statement<'ast, Expression<'ast>>> {
+    let mut attributes = vec![];
+    while parser.lexer.peek_expect(TokenKind::Hash).is_some() {
+        attributes.push(parse_attribute(parser)?);
+    }
+
+    let span = SourceSpan::combine(attributes.iter().map(|a| a.span).collect());
+
+    let mut statement = match parser.lexer.peek() {
+        Some(Ok(token)) if token.kind == TokenKind::Function => parse_function(parser)?,
+        Some(Ok(token)) if token.kind == TokenKind::Struct => parse_struct(parser)?,
+        _ => {
+            return Err(miette::miette! {
+                labels = vec![LabeledSpan::at(span, "attributes")],
+                help = "attributes can only be placed before a function or a struct",
+                "expected a function or a struct after attributes"
+            })
+        }
+    };
+
+    match &mut statement.value {
+        StatementValue::Function { header, .. } => header.attributes = attributes,
+        StatementValue::Struct {
+            attributes: declared,
+            ..
+        } => *declared = attributes,
+        _ => unreachable!(),
+    }
+
+    statement.span = SourceSpan::combine(vec![span, statement.span]);
+
+    Ok(statement)
+}
+
+fn parse_attribute<'ast>(parser: &mut Parser<'ast>) -> Result<Attribute<'ast>> {
+    let token = parser
+        .lexer
+        .expect(TokenKind::Hash, "expected a hash sign")?;
+
+    parser
+        .lexer
+        .expect(TokenKind::SquareOpen, "expected an open square bracket")?;
+
+    let identifier = parser
+        .lexer
+        .expect(TokenKind::Identifier, "expected an attribute name")?;
+
+    let name = match identifier.value {
+        TokenValue::Identifier(name, span) => Identifier { name, span },
+        _ => unreachable!(),
+    };
+
+    let mut arguments = vec![];
+
+    if parser.lexer.peek_expect(TokenKind::ParenOpen).is_some() {
+        parser.lexer.next();
+
+        while parser.lexer.peek().is_some_and(|token| {
+            token
+                .as_ref()
+                .is_ok_and(|token| token.kind != TokenKind::ParenClose)
+        }) {
+            if !arguments.is_empty() {
+                parser
+                    .lexer
+                    .expect(TokenKind::Comma, "expected a comma between arguments")?;
+                if parser.lexer.peek_expect(TokenKind::ParenClose).is_some() {
+                    break;
+                }
+            }
+
+            let argument = expression::parse(parser, BindingPower::None)?;
+
+            match argument.value {
+                ExpressionValue::Primitive(primitive) => arguments.push(primitive),
+                _ => {
+                    return Err(miette::miette! {
+                        labels = vec![argument.label("attribute argument")],
+                        help = "attribute arguments can only be literals or names",
+                        "invalid attribute argument"
+                    })
+                }
+            }
+        }
+
+        parser
+            .lexer
+            .expect(TokenKind::ParenClose, "expected a close parenthesis")?;
+    }
+
+    let close = parser
+        .lexer
+        .expect(TokenKind::SquareClose, "expected a close square bracket")?;
+
+    Ok(Attribute {
+        name,
+        arguments,
+        span: SourceSpan::combine(vec![token.span, close.span]),
+    })
+}
+
 pub fn parse_enum<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, Expression<'ast>>> {
     let token = parser
         .lexer
@@ -151,7 +329,7 @@ pub fn parse_enum<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, Exp
         .expect(TokenKind::Identifier, "expected an enum name")?;
 
     let name = match identifier.value {
-        TokenValue::Identifier(identifier) => identifier,
+        TokenValue::Identifier(identifier, _) => identifier,
         _ => unreachable!(),
     };
 
@@ -168,6 +346,9 @@ pub fn parse_enum<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, Exp
             parser
                 .lexer
                 .expect(TokenKind::Comma, "expected a comma between enum members")?;
+            if parser.lexer.peek_expect(TokenKind::Semicolon).is_some() {
+                break;
+            }
         }
 
         let variant = parser
@@ -175,7 +356,7 @@ pub fn parse_enum<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, Exp
             .expect(TokenKind::Identifier, "expected an enum member name")?;
 
         let variant_name = match variant.value {
-            TokenValue::Identifier(variant) => variant,
+            TokenValue::Identifier(variant, _) => variant,
             _ => unreachable!(),
         };
 
@@ -218,7 +399,7 @@ pub fn parse_trait<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, Ex
         .expect(TokenKind::Identifier, "expected a trait name")?;
 
     let name = match identifier.value {
-        TokenValue::Identifier(identifier) => identifier,
+        TokenValue::Identifier(identifier, _) => identifier,
         _ => unreachable!(),
     };
 
@@ -235,6 +416,9 @@ pub fn parse_trait<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, Ex
             parser
                 .lexer
                 .expect(TokenKind::Comma, "expected a comma between functions")?;
+            if parser.lexer.peek_expect(TokenKind::Semicolon).is_some() {
+                break;
+            }
         }
 
         functions.push(parse_function_header(parser)?);
@@ -305,10 +489,12 @@ fn parse_function_header<'ast>(parser: &mut Parser<'ast>) -> Result<FunctionHead
         .expect(TokenKind::Identifier, "expected function name")?;
 
     let name = match identifier.value {
-        TokenValue::Identifier(identifier) => identifier,
+        TokenValue::Identifier(identifier, _) => identifier,
         _ => unreachable!(),
     };
 
+    let type_parameters = parse_type_parameters(parser)?;
+
     parser
         .lexer
         .expect(TokenKind::ParenOpen, "expected an open parenthesis")?;
@@ -324,6 +510,9 @@ fn parse_function_header<'ast>(parser: &mut Parser<'ast>) -> Result<FunctionHead
             parser
                 .lexer
                 .expect(TokenKind::Comma, "expected a comma between parameters")?;
+            if parser.lexer.peek_expect(TokenKind::ParenClose).is_some() {
+                break;
+            }
         }
 
         let parameter = parser
@@ -331,7 +520,7 @@ fn parse_function_header<'ast>(parser: &mut Parser<'ast>) -> Result<FunctionHead
             .expect(TokenKind::Identifier, "expected a parameter name")?;
 
         let parameter_name = match parameter.value {
-            TokenValue::Identifier(parameter) => parameter,
+            TokenValue::Identifier(parameter, _) => parameter,
             _ => unreachable!(),
         };
 
@@ -364,13 +553,56 @@ fn parse_function_header<'ast>(parser: &mut Parser<'ast>) -> Result<FunctionHead
     }
 
     Ok(FunctionHeader {
+        attributes: vec![],
         span: SourceSpan::combine(spans),
         name,
+        type_parameters,
         parameters,
         explicit_return_type,
     })
 }
 
+/// Parses the type parameters of a generic declaration, such as the `<T, U>` in
+/// `fn pair<T, U>(a ~ T, b ~ U)`. Declarations without them have none.
+fn parse_type_parameters<'ast>(parser: &mut Parser<'ast>) -> Result<Vec<Identifier<'ast>>> {
+    let mut type_parameters = vec![];
+
+    if parser.lexer.peek_expect(TokenKind::LessThan).is_none() {
+        return Ok(type_parameters);
+    }
+    parser.lexer.next();
+
+    while parser.lexer.peek().is_some_and(|token| {
+        token
+            .as_ref()
+            .is_ok_and(|token| token.kind != TokenKind::GreaterThan)
+    }) {
+        if !type_parameters.is_empty() {
+            parser
+                .lexer
+                .expect(TokenKind::Comma, "expected a comma between type parameters")?;
+            if parser.lexer.peek_expect(TokenKind::GreaterThan).is_some() {
+                break;
+            }
+        }
+
+        let type_parameter = parser
+            .lexer
+            .expect(TokenKind::Identifier, "expected a type parameter name")?;
+
+        type_parameters.push(match type_parameter.value {
+            TokenValue::Identifier(name, span) => Identifier { name, span },
+            _ => unreachable!(),
+        });
+    }
+
+    parser
+        .lexer
+        .expect(TokenKind::GreaterThan, "expected a closing angle bracket")?;
+
+    Ok(type_parameters)
+}
+
 pub fn parse_type<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, Expression<'ast>>> {
     let token = parser
         .lexer
@@ -381,7 +613,7 @@ pub fn parse_type<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, Exp
         .expect(TokenKind::Identifier, "expected a type name")?;
 
     let name = match identifier.value {
-        TokenValue::Identifier(identifier) => identifier,
+        TokenValue::Identifier(identifier, _) => identifier,
         _ => unreachable!(),
     };
 
@@ -405,7 +637,13 @@ pub fn parse_type<'ast>(parser: &mut Parser<'ast>) -> Result<Statement<'ast, Exp
             },
         ))
     } else {
-        // type definition
-        todo!()
+        Err(miette::miette! {
+            labels = vec![LabeledSpan::at(
+                SourceSpan::combine(vec![token.span, identifier.span]),
+                "type definition",
+            )],
+            help = format!("type definitions are not supported yet, alias a type with `type {} = ...;`", name),
+            "feature not yet implemented"
+        })
     }
 }
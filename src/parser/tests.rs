@@ -0,0 +1,600 @@
+use super::*;
+use crate::{
+    ast::{ExpressionValue, Primitive, StatementValue},
+    lexer::TokenKind,
+};
+use miette::SourceSpan;
+
+#[test]
+fn empty_program() {
+    for code in ["", "   \n", "\t\r\n  "] {
+        let errors = Parser::new(Lexer::new(code)).parse().unwrap_err();
+        let error = &errors[0];
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(error.to_string(), "empty program");
+        assert_eq!(error.labels().unwrap().next().unwrap().offset(), 0);
+    }
+}
+
+#[test]
+fn expect_on_empty_input() {
+    let error = Lexer::new("")
+        .expect(TokenKind::Function, "expected a function")
+        .unwrap_err();
+
+    assert_eq!(error.labels().unwrap().next().unwrap().offset(), 0);
+}
+
+#[test]
+fn path_expression() {
+    let module = Parser::new(Lexer::new("fn main() { std::io::print(1) }"))
+        .parse()
+        .unwrap();
+
+    let body = match &module.definitions[0].value {
+        StatementValue::Function { body, .. } => body,
+        _ => panic!("expected a function"),
+    };
+
+    let callee = match &body.value {
+        ExpressionValue::Group(block) => match &block.value {
+            ExpressionValue::Block { return_value, .. } => match &return_value.value {
+                ExpressionValue::Call { callee, .. } => callee,
+                _ => panic!("expected a call"),
+            },
+            _ => panic!("expected a block"),
+        },
+        _ => panic!("expected a group"),
+    };
+
+    match &callee.value {
+        ExpressionValue::Path(path) => {
+            let names = path.segments.iter().map(|s| &s.name).collect::<Vec<_>>();
+            assert_eq!(names, ["std", "io", "print"]);
+
+            let source = "fn main() { std::io::print(1) }";
+            let offset = source.find("io").unwrap();
+            assert_eq!(path.segments[1].span, SourceSpan::new(offset.into(), 2));
+        }
+        _ => panic!("expected a path"),
+    }
+
+    let source = "fn main() { std::io::print(1) }";
+    let offset = source.find("std").unwrap();
+    assert_eq!(
+        callee.span,
+        SourceSpan::new(offset.into(), "std::io::print".len())
+    );
+}
+
+#[test]
+fn path_after_non_name() {
+    let errors = Parser::new(Lexer::new("fn main() { 1::print }"))
+        .parse()
+        .unwrap_err();
+
+    assert!(format!("{:?}", errors[0]).contains("expected a name before `::`"));
+}
+
+#[test]
+fn recover_from_broken_statement() {
+    let code = "fn main() {\n  let a = 1;\n  let = 2;\n  let c = a;\n}\n\nfn helper() {}";
+
+    let errors = Parser::new(Lexer::new(code)).parse().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(format!("{:?}", errors[0]).contains("expected a variable name"));
+}
+
+#[test]
+fn recover_from_broken_definition() {
+    let code = "fn first() {}\nfn ( {}\nfn second() { let a = ; }\nfn third() {}";
+
+    let mut parser = Parser::new(Lexer::new(code));
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn recovered_statements_are_kept() {
+    let code = "{\n  let a = 1;\n  let = 2;\n  let c = a;\n}";
+
+    let mut parser = Parser::new(Lexer::new(code));
+    let block = expression::parse(&mut parser, lookup::BindingPower::None).unwrap();
+    assert_eq!(parser.errors.len(), 1);
+
+    let statements = match block.value {
+        ExpressionValue::Group(block) => match block.value {
+            ExpressionValue::Block { statements, .. } => statements,
+            _ => panic!("expected a block"),
+        },
+        _ => panic!("expected a group"),
+    };
+
+    let names = statements
+        .iter()
+        .map(|statement| match &statement.value {
+            StatementValue::Assignment { name, .. } => name.as_ref(),
+            _ => panic!("expected an assignment"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a", "c"]);
+}
+
+#[test]
+fn unterminated_block() {
+    for code in ["fn main() {", "fn main() { let a = 1;"] {
+        let errors = Parser::new(Lexer::new(code)).parse().unwrap_err();
+
+        assert!(errors[0]
+            .chain()
+            .any(|error| error.to_string() == "unexpected end of input"));
+    }
+
+    let mut parser = Parser::new(Lexer::new("{"));
+    let error = expression::parse(&mut parser, lookup::BindingPower::None).unwrap_err();
+    assert!(error
+        .chain()
+        .any(|error| error.to_string() == "unexpected end of input"));
+}
+
+#[test]
+fn trailing_expression_after_recovery() {
+    let code = "{\n  let = 2;\n  let a = 1;\n  a\n}";
+
+    let mut parser = Parser::new(Lexer::new(code));
+    let block = expression::parse(&mut parser, lookup::BindingPower::None).unwrap();
+    assert_eq!(parser.errors.len(), 1);
+
+    match block.value {
+        ExpressionValue::Group(block) => match block.value {
+            ExpressionValue::Block { return_value, .. } => {
+                assert!(matches!(
+                    return_value.value,
+                    ExpressionValue::Primitive(Primitive::Identifier(_))
+                ));
+            }
+            _ => panic!("expected a block"),
+        },
+        _ => panic!("expected a group"),
+    }
+}
+
+#[test]
+fn deeply_nested_expression() {
+    let code = format!("fn main() {{ {}1 }}", "(".repeat(100_000));
+    let errors = Parser::new(Lexer::new(&code)).parse().unwrap_err();
+
+    assert!(errors[0]
+        .chain()
+        .any(|error| error.to_string() == "expression nesting too deep"));
+}
+
+#[test]
+fn type_definition_not_implemented() {
+    let errors = Parser::new(Lexer::new("type Point { x ~ i32 }"))
+        .parse()
+        .unwrap_err();
+
+    assert_eq!(errors[0].to_string(), "feature not yet implemented");
+}
+
+#[test]
+fn generic_function() {
+    let module = Parser::new(Lexer::new("fn pair<T, U>(a ~ T, b ~ U) -> T { a }"))
+        .parse()
+        .unwrap();
+
+    let header = match &module.definitions[0].value {
+        StatementValue::Function { header, .. } => header,
+        _ => panic!("expected a function"),
+    };
+
+    let names = header
+        .type_parameters
+        .iter()
+        .map(|parameter| parameter.name.as_ref())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["T", "U"]);
+    assert_eq!(
+        header.type_parameters[1].span,
+        SourceSpan::new(11.into(), 1)
+    );
+}
+
+#[test]
+fn generic_struct() {
+    let module = Parser::new(Lexer::new("struct Box<T>: value ~ T;"))
+        .parse()
+        .unwrap();
+
+    match &module.definitions[0].value {
+        StatementValue::Struct {
+            type_parameters,
+            fields,
+            ..
+        } => {
+            assert_eq!(type_parameters.len(), 1);
+            assert_eq!(type_parameters[0].name, "T");
+            assert_eq!(fields.len(), 1);
+        }
+        _ => panic!("expected a struct"),
+    }
+}
+
+#[test]
+fn function_attributes() {
+    let module = Parser::new(Lexer::new(
+        "#[inline] #[deprecated(\"use add\", 2)] fn plus(a ~ i32) -> i32 { a }",
+    ))
+    .parse()
+    .unwrap();
+
+    let header = match &module.definitions[0].value {
+        StatementValue::Function { header, .. } => header,
+        _ => panic!("expected a function"),
+    };
+
+    assert_eq!(header.attributes.len(), 2);
+    assert_eq!(header.attributes[0].name.name, "inline");
+    assert!(header.attributes[0].arguments.is_empty());
+    assert_eq!(header.attributes[1].name.name, "deprecated");
+    assert_eq!(
+        header.attributes[1].arguments,
+        [Primitive::String("use add".into()), Primitive::Integer(2)]
+    );
+    assert_eq!(module.definitions[0].span.offset(), 0);
+}
+
+#[test]
+fn attributes_without_declaration() {
+    let errors = Parser::new(Lexer::new("#[inline] let a = 1;"))
+        .parse()
+        .unwrap_err();
+
+    assert_eq!(
+        errors[0].to_string(),
+        "expected a function or a struct after attributes"
+    );
+}
+
+#[test]
+fn interpolated_string() {
+    let module = Parser::new(Lexer::new(r#"fn main() { "a ${b} c" }"#))
+        .parse()
+        .unwrap();
+
+    let body = match &module.definitions[0].value {
+        StatementValue::Function { body, .. } => body,
+        _ => panic!("expected a function"),
+    };
+
+    let string = match &body.value {
+        ExpressionValue::Group(block) => match &block.value {
+            ExpressionValue::Block { return_value, .. } => return_value,
+            _ => panic!("expected a block"),
+        },
+        _ => panic!("expected a group"),
+    };
+
+    let parts = match &string.value {
+        ExpressionValue::Interpolation(parts) => parts,
+        _ => panic!("expected an interpolated string"),
+    };
+
+    assert_eq!(parts.len(), 3);
+    assert!(matches!(
+        parts[1].value,
+        ExpressionValue::Primitive(Primitive::Identifier(ref name)) if name == "b"
+    ));
+    assert_eq!(parts[0].span, SourceSpan::new(13.into(), 2));
+    assert_eq!(parts[1].span, SourceSpan::new(17.into(), 1));
+    assert_eq!(parts[2].span, SourceSpan::new(19.into(), 2));
+
+    let errors = Parser::new(Lexer::new(r#"fn main() { "${b c}" }"#))
+        .parse()
+        .unwrap_err();
+    assert!(errors[0].chain().any(|error| error.to_string()
+        == "expected `}` after the embedded expression, found an identifier"));
+}
+
+#[test]
+fn errors_in_embedded_expressions_are_kept() {
+    let code = r#"fn main() { let s = "${ { let a = 1 a } }"; }"#;
+
+    let errors = Parser::new(Lexer::new(code)).parse().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "missing semicolon");
+    assert_eq!(errors[0].labels().unwrap().next().unwrap().offset(), 35);
+}
+
+#[test]
+fn missing_semicolon() {
+    let code = "fn main() {\n    let a = 1\n    let b = 2;\n    b\n}";
+    let mut parser = Parser::new(Lexer::new(code));
+    let errors = parser.parse().unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "missing semicolon");
+    assert_eq!(
+        errors[0].labels().unwrap().next().unwrap().offset(),
+        code.find("1\n").unwrap() + 1
+    );
+}
+
+#[test]
+fn statements_after_missing_semicolon() {
+    let code = "fn main() { let a = 1 let b = 2; b }";
+    let mut parser = Parser::new(Lexer::new(code));
+    let block = match crate::parser::statement::parse(&mut parser, false)
+        .unwrap()
+        .value
+    {
+        StatementValue::Function { body, .. } => body,
+        _ => panic!("expected a function"),
+    };
+
+    match block.value {
+        ExpressionValue::Group(block) => match block.value {
+            ExpressionValue::Block {
+                statements,
+                return_value,
+            } => {
+                assert_eq!(statements.len(), 2);
+                assert!(matches!(
+                    return_value.value,
+                    ExpressionValue::Primitive(Primitive::Identifier(_))
+                ));
+            }
+            _ => panic!("expected a block"),
+        },
+        _ => panic!("expected a group"),
+    }
+}
+
+#[test]
+fn keyword_as_name() {
+    for (code, keyword) in [
+        ("fn main() { let fn = 1; }", "fn"),
+        ("fn main() { let while = 1; }", "while"),
+        ("fn main(for ~ i32) {}", "for"),
+        ("fn struct() {}", "struct"),
+        ("fn main(i64 ~ i32) {}", "i64"),
+    ] {
+        let errors = Parser::new(Lexer::new(code)).parse().unwrap_err();
+        let message = format!(
+            "`{}` is a reserved keyword and can't be used as a name",
+            keyword
+        );
+
+        assert!(
+            errors[0].chain().any(|error| error.to_string() == message),
+            "{}",
+            code
+        );
+    }
+
+    for code in [
+        r#"fn main() { let r"x" = 1; }"#,
+        "fn main() { let true = 1; }",
+    ] {
+        let errors = Parser::new(Lexer::new(code)).parse().unwrap_err();
+
+        assert!(
+            !errors[0]
+                .chain()
+                .any(|error| error.to_string().contains("reserved keyword")),
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn lexer_errors_are_kept() {
+    let code = r#"fn main() { let a = "\q"; }"#;
+    let errors = Parser::new(Lexer::new(code)).parse().unwrap_err();
+    let error = errors[0]
+        .chain()
+        .find(|error| error.to_string() == "invalid escape sequence");
+
+    assert!(error.is_some());
+    assert_eq!(
+        errors[0].labels().unwrap().next().unwrap().offset(),
+        code.find('\\').unwrap()
+    );
+    assert!(errors[0].help().is_some());
+}
+
+#[test]
+fn arithmetic_is_left_associative() {
+    let mut parser = Parser::new(Lexer::new("a - b - c"));
+    let expression = expression::parse(&mut parser, lookup::BindingPower::None).unwrap();
+
+    match expression.value {
+        ExpressionValue::Binary { left, right, .. } => {
+            assert!(matches!(left.value, ExpressionValue::Binary { .. }));
+            assert!(matches!(right.value, ExpressionValue::Primitive(_)));
+        }
+        _ => panic!("expected a binary expression"),
+    }
+}
+
+#[test]
+fn unary_operators_bind_tighter_than_binary_ones() {
+    for code in ["-a - b", "!a == b"] {
+        let mut parser = Parser::new(Lexer::new(code));
+        let expression = expression::parse(&mut parser, lookup::BindingPower::None).unwrap();
+
+        match expression.value {
+            ExpressionValue::Binary { left, right, .. } => {
+                assert!(
+                    matches!(left.value, ExpressionValue::Unary { .. }),
+                    "{code}"
+                );
+                assert!(
+                    matches!(right.value, ExpressionValue::Primitive(_)),
+                    "{code}"
+                );
+            }
+            _ => panic!("expected a binary expression for {code}"),
+        }
+    }
+}
+
+#[test]
+fn conditional_is_right_associative() {
+    let mut parser = Parser::new(Lexer::new("a if b else c if d else e"));
+    let expression = expression::parse(&mut parser, lookup::BindingPower::None).unwrap();
+
+    match expression.value {
+        ExpressionValue::Conditional { truthy, falsy, .. } => {
+            assert!(matches!(truthy.value, ExpressionValue::Primitive(_)));
+            assert!(matches!(falsy.value, ExpressionValue::Conditional { .. }));
+        }
+        _ => panic!("expected a conditional expression"),
+    }
+}
+
+#[test]
+fn adjacent_strings_are_joined() {
+    let mut parser = Parser::new(Lexer::new(r#""foo" "bar\n"  "baz""#));
+    let expression = expression::parse(&mut parser, lookup::BindingPower::None).unwrap();
+
+    assert!(matches!(
+        expression.value,
+        ExpressionValue::Primitive(Primitive::String(ref value)) if value == "foobar\nbaz"
+    ));
+    assert_eq!(expression.span, SourceSpan::new(0.into(), 20));
+
+    let mut parser = Parser::new(Lexer::new(r#""foo" "x = ${x}" "!""#));
+    let expression = expression::parse(&mut parser, lookup::BindingPower::None).unwrap();
+
+    let parts = match expression.value {
+        ExpressionValue::Interpolation(parts) => parts,
+        _ => panic!("expected an interpolated string"),
+    };
+    assert_eq!(parts.len(), 4);
+    assert!(matches!(
+        parts[2].value,
+        ExpressionValue::Primitive(Primitive::Identifier(ref name)) if name == "x"
+    ));
+    assert_eq!(expression.span, SourceSpan::new(0.into(), 20));
+
+    let mut parser = Parser::new(Lexer::new(r#""foo" + "bar""#));
+    let expression = expression::parse(&mut parser, lookup::BindingPower::None).unwrap();
+
+    assert!(matches!(expression.value, ExpressionValue::Binary { .. }));
+}
+
+#[test]
+fn trailing_commas() {
+    for code in [
+        "fn main() { f(a, b,) }",
+        "fn main() { |a ~ i32, b ~ i32,| a }",
+        "fn add(a ~ i32, b ~ i32,) -> i32 { a + b }",
+        "fn id<T,>(a ~ T) -> T { a }",
+        "struct Point: x ~ i32, y ~ i32,;",
+        "#[inline(1, 2,)] fn main() {}",
+    ] {
+        assert!(Parser::new(Lexer::new(code)).parse().is_ok(), "{}", code);
+    }
+
+    for code in ["fn main() { f(,) }", "fn main() { f(a,,) }", "fn add(,) {}"] {
+        assert!(Parser::new(Lexer::new(code)).parse().is_err(), "{}", code);
+    }
+}
+
+#[test]
+fn misspelled_keyword() {
+    let errors = Parser::new(Lexer::new("fn main() { lets x = 1; }"))
+        .parse()
+        .unwrap_err();
+
+    assert!(errors
+        .iter()
+        .any(|error| error.to_string() == "unknown keyword `lets`"
+            && error.help().unwrap().to_string() == "did you mean `let`?"));
+
+    let errors = Parser::new(Lexer::new("fnc main() {}"))
+        .parse()
+        .unwrap_err();
+    assert!(errors.iter().any(|error| error
+        .help()
+        .is_some_and(|help| help.to_string() == "did you mean `fn`?")));
+
+    assert!(Parser::new(Lexer::new("fn main() { lets; }"))
+        .parse()
+        .is_ok());
+}
+
+#[test]
+fn unsupported_width_suffix() {
+    let errors = Parser::new(Lexer::new("fn main() { 5u8 }"))
+        .parse()
+        .unwrap_err();
+
+    assert!(errors[0]
+        .chain()
+        .any(|error| error.to_string() == "feature not yet implemented"));
+}
+
+#[test]
+fn negative_suffixed_integers() {
+    for (code, value) in [
+        ("-2147483648i", Primitive::Int32(i32::MIN.into())),
+        ("-9223372036854775808l", Primitive::Int64(i64::MIN)),
+        ("-5i", Primitive::Int32(-5)),
+    ] {
+        let mut parser = Parser::new(Lexer::new(code));
+        let expression = expression::parse(&mut parser, lookup::BindingPower::None).unwrap();
+
+        assert!(
+            matches!(expression.value, ExpressionValue::Primitive(ref actual) if *actual == value),
+            "{}",
+            code
+        );
+        assert_eq!(expression.span, SourceSpan::new(0.into(), code.len()));
+    }
+
+    for (code, help) in [
+        (
+            "2147483648i",
+            "2147483648 does not fit in a 32-bit integer, use the `l` suffix for a 64-bit integer",
+        ),
+        ("128i8", "128 does not fit in an 8-bit integer"),
+        ("-129i8", "129 does not fit in an 8-bit integer"),
+        ("-1u8", "-1 does not fit in an unsigned 8-bit integer"),
+    ] {
+        let mut parser = Parser::new(Lexer::new(code));
+        let error = expression::parse(&mut parser, lookup::BindingPower::None).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "integer literal out of range",
+            "{}",
+            code
+        );
+        assert_eq!(error.help().unwrap().to_string(), help, "{}", code);
+    }
+
+    let errors = Parser::new(Lexer::new("fn main() { -128i8 }"))
+        .parse()
+        .unwrap_err();
+    assert!(errors[0]
+        .chain()
+        .any(|error| error.to_string() == "feature not yet implemented"));
+}
+
+#[test]
+fn doc_comments_are_skipped() {
+    for code in [
+        "/// The entry point.\n/// Runs first.\nfn main() {\n    /// A number.\n    let a = 1;\n    a\n}",
+        "fn main() {}\n/// Nothing after this.",
+        "fn main() { 1; /** dangling */ }",
+        "fn f(a ~ i32, b ~ i32) -> i32 { a }\nfn main() { f(1, /// note\n 2) }",
+    ] {
+        let result = Parser::new(Lexer::new(code)).parse();
+        assert!(result.is_ok(), "{}", code);
+    }
+}
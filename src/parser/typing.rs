@@ -8,7 +8,7 @@ use miette::Result;
 pub fn parse<'ast>(parser: &mut Parser<'ast>, binding_power: BindingPower) -> Result<Type<'ast>> {
     let token = match parser.lexer.peek().as_ref() {
         Some(Ok(token)) => token,
-        Some(Err(err)) => return Err(miette::miette!(err.to_string())), // FIXME: better error handling
+        Some(Err(_)) => return Err(parser.lexer_error()),
         None => {
             return Err(miette::miette! {
                 help = "expected a type",
@@ -33,7 +33,7 @@ pub fn parse<'ast>(parser: &mut Parser<'ast>, binding_power: BindingPower) -> Re
     while let Some(token) = next_token {
         let token = match token {
             Ok(token) => token,
-            Err(err) => return Err(miette::miette!(err.to_string())), // FIXME: better error handling
+            Err(_) => return Err(parser.lexer_error()),
         };
 
         let token_binding_power = {
@@ -94,6 +94,14 @@ pub fn parse_integer<'ast>(parser: &mut Parser<'ast>) -> Result<Type<'ast>> {
     Ok(Type::at(token.span, TypeValue::Integer))
 }
 
+pub fn parse_long<'ast>(parser: &mut Parser<'ast>) -> Result<Type<'ast>> {
+    let token = parser
+        .lexer
+        .expect(TokenKind::LongType, "expected a long integer type")?;
+
+    Ok(Type::at(token.span, TypeValue::Long))
+}
+
 pub fn parse_decimal<'ast>(parser: &mut Parser<'ast>) -> Result<Type<'ast>> {
     let token = parser
         .lexer
@@ -156,7 +164,7 @@ pub fn parse_identifier<'ast>(parser: &mut Parser<'ast>) -> Result<Type<'ast>> {
         .expect(TokenKind::Identifier, "expected an identifier")?;
 
     let name = match token.value {
-        TokenValue::Identifier(identifier) => identifier,
+        TokenValue::Identifier(identifier, _) => identifier,
         _ => unreachable!(),
     };
 
@@ -183,6 +191,9 @@ pub fn parse_function<'ast>(parser: &mut Parser<'ast>) -> Result<Type<'ast>> {
             parser
                 .lexer
                 .expect(TokenKind::Comma, "expected a comma between parameters")?;
+            if parser.lexer.peek_expect(TokenKind::ParenClose).is_some() {
+                break;
+            }
         }
 
         let parameter = parse(parser, BindingPower::None)?;
@@ -210,6 +221,7 @@ pub fn parse_function<'ast>(parser: &mut Parser<'ast>) -> Result<Type<'ast>> {
         ],
         TypeValue::Function {
             parameters,
+            parameter_names: None,
             return_type: Box::new(explicit_return_type.unwrap_or_else(|| Type::unit(open.span))),
         },
     ))
@@ -0,0 +1,121 @@
+use crate::{ast::DEFAULT_TAB_WIDTH, highlighter::SomHighlighter};
+use miette::MietteDiagnostic;
+use std::io::IsTerminal;
+
+#[cfg(test)]
+mod tests;
+
+/// Whether diagnostics are rendered with colors, as set with `--color=always|never|auto`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    Always,
+    Never,
+    /// Colors are used when writing to a terminal, unless `NO_COLOR` is set.
+    #[default]
+    Auto,
+}
+
+impl ColorChoice {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            "auto" => Some(ColorChoice::Auto),
+            _ => None,
+        }
+    }
+
+    /// Finds the last `--color=...` argument, defaulting to [`ColorChoice::Auto`].
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        args.into_iter()
+            .filter_map(|arg| arg.strip_prefix("--color=").and_then(Self::parse))
+            .last()
+            .unwrap_or_default()
+    }
+
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+
+                !no_color && std::io::stdout().is_terminal() && std::io::stderr().is_terminal()
+            }
+        }
+    }
+}
+
+/// Finds the last valid `--tab-width=...` argument, defaulting to [`DEFAULT_TAB_WIDTH`].
+pub fn tab_width_from_args(args: impl IntoIterator<Item = String>) -> usize {
+    args.into_iter()
+        .filter_map(|arg| arg.strip_prefix("--tab-width=")?.parse().ok())
+        .filter(|width| *width > 0)
+        .last()
+        .unwrap_or(DEFAULT_TAB_WIDTH)
+}
+
+/// The handler diagnostics are rendered with, with tabs expanded to `tab_width` columns.
+/// Without colors, syntax highlighting is turned off as well, so the output contains no escape
+/// sequences.
+pub fn handler(color: ColorChoice, tab_width: usize) -> miette::MietteHandler {
+    let options = miette::MietteHandlerOpts::new()
+        .terminal_links(true)
+        .unicode(true)
+        .tab_width(tab_width)
+        .context_lines(2);
+
+    if color.enabled() {
+        options
+            .color(true)
+            .with_syntax_highlighting(SomHighlighter {})
+            .build()
+    } else {
+        options
+            .color(false)
+            .force_graphical(true)
+            .without_syntax_highlighting()
+            .build()
+    }
+}
+
+/// Merges diagnostics that only differ in where they point, such as the same undeclared
+/// variable used several times, into the first of them with the labels of all of them. Labels
+/// at the same span are only kept once, and labels are sorted into source order.
+pub fn deduplicate(diagnostics: Vec<MietteDiagnostic>) -> Vec<MietteDiagnostic> {
+    let mut merged: Vec<MietteDiagnostic> = vec![];
+
+    for diagnostic in diagnostics {
+        let existing = merged.iter_mut().find(|existing| {
+            existing.message == diagnostic.message
+                && existing.code == diagnostic.code
+                && existing.severity == diagnostic.severity
+                && existing.help == diagnostic.help
+        });
+
+        let existing = match existing {
+            Some(existing) => existing,
+            None => {
+                merged.push(diagnostic);
+                continue;
+            }
+        };
+
+        let labels = existing.labels.get_or_insert_with(Vec::new);
+        for label in diagnostic.labels.into_iter().flatten() {
+            if !labels.iter().any(|existing| {
+                existing.offset() == label.offset() && existing.len() == label.len()
+            }) {
+                labels.push(label);
+            }
+        }
+    }
+
+    for diagnostic in &mut merged {
+        if let Some(labels) = &mut diagnostic.labels {
+            labels.sort_by_key(|label| (label.offset(), label.len()));
+        }
+    }
+
+    merged
+}
@@ -0,0 +1,146 @@
+use super::*;
+use miette::{Diagnostic, LabeledSpan, MietteDiagnostic, ReportHandler};
+use std::fmt::{Debug, Formatter};
+
+#[test]
+fn color_from_args() {
+    let args = |args: &[&str]| ColorChoice::from_args(args.iter().map(|a| a.to_string()));
+
+    assert_eq!(args(&[]), ColorChoice::Auto);
+    assert_eq!(args(&["--color=never"]), ColorChoice::Never);
+    assert_eq!(
+        args(&["--color=never", "--color=always"]),
+        ColorChoice::Always
+    );
+    assert_eq!(args(&["--color=sometimes"]), ColorChoice::Auto);
+}
+
+#[test]
+fn tab_width_from_args() {
+    let args = |args: &[&str]| super::tab_width_from_args(args.iter().map(|a| a.to_string()));
+
+    assert_eq!(args(&[]), DEFAULT_TAB_WIDTH);
+    assert_eq!(args(&["--tab-width=4"]), 4);
+    assert_eq!(args(&["--tab-width=4", "--tab-width=2"]), 2);
+    assert_eq!(args(&["--tab-width=0"]), DEFAULT_TAB_WIDTH);
+    assert_eq!(args(&["--tab-width=wide"]), DEFAULT_TAB_WIDTH);
+}
+
+#[test]
+fn render_with_tab_width() {
+    let source = "fn main() {\n\tlet a ~ bool = 1;\n}";
+    let diagnostic = MietteDiagnostic::new("the value must match the declared type")
+        .with_label(LabeledSpan::at(28..29, "an integer"));
+    let report = miette::Report::new(diagnostic).with_source_code(source);
+
+    let output = format!(
+        "{:?}",
+        Render(handler(ColorChoice::Never, 4), report.as_ref())
+    );
+
+    assert!(output.contains("\n 2 │     let a ~ bool = 1;\n"));
+}
+
+#[test]
+fn render_without_color() {
+    let source = "fn main() {\n    let a ~ bool = 1;\n}";
+    let diagnostic = MietteDiagnostic::new("the value must match the declared type")
+        .with_label(LabeledSpan::at(31..32, "an integer"));
+    let report = miette::Report::new(diagnostic).with_source_code(source);
+
+    let output = format!(
+        "{:?}",
+        Render(
+            handler(ColorChoice::Never, DEFAULT_TAB_WIDTH),
+            report.as_ref()
+        )
+    );
+
+    assert!(output.contains("the value must match the declared type"));
+    assert!(output.contains("let a ~ bool = 1;"));
+    assert!(!output.contains('\u{1b}'));
+}
+
+struct Render<'a>(miette::MietteHandler, &'a dyn Diagnostic);
+
+impl Debug for Render<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.debug(self.1, f)
+    }
+}
+
+#[test]
+fn deduplicate_repeated_errors() {
+    let source = "fn main() {\n    let a = x;\n    let b = x;\n    let c = x;\n}";
+    let (_, diagnostics) = crate::check(source);
+    let errors = diagnostics
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity.is_none())
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 3);
+
+    let errors = deduplicate(errors);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].labels.as_ref().unwrap().len(), 3);
+
+    let repeated = deduplicate(vec![errors[0].clone(), errors[0].clone()]);
+    assert_eq!(repeated.len(), 1);
+    assert_eq!(repeated[0].labels.as_ref().unwrap().len(), 3);
+}
+
+#[test]
+fn render_after_multibyte_characters() {
+    let source = "fn main() {\n    let a = \"héllo wörld\" + x;\n}";
+    let (_, diagnostics) = crate::check(source);
+    let diagnostic = diagnostics
+        .into_iter()
+        .find(|diagnostic| diagnostic.severity.is_none())
+        .unwrap();
+    let report = miette::Report::new(diagnostic).with_source_code(source);
+
+    let output = format!(
+        "{:?}",
+        Render(
+            handler(ColorChoice::Never, DEFAULT_TAB_WIDTH),
+            report.as_ref()
+        )
+    );
+    let line = output.lines().find(|line| line.contains("let a")).unwrap();
+    let caret = output.lines().find(|line| line.contains('┬')).unwrap();
+
+    // Spans are in bytes, but the caret is placed by characters
+    assert_eq!(
+        caret.chars().position(|c| c == '┬'),
+        line.chars().position(|c| c == 'x')
+    );
+}
+
+#[test]
+fn labels_in_source_order() {
+    let source = "fn main() {\n    let a = 1;\n    let a = 2;\n}";
+    let diagnostic = MietteDiagnostic::new("`a` is already declared").with_labels(vec![
+        LabeledSpan::at(35..36, "redeclared here"),
+        LabeledSpan::at(20..21, "first declared here"),
+    ]);
+
+    let diagnostics = deduplicate(vec![diagnostic]);
+    let offsets = diagnostics[0]
+        .labels
+        .iter()
+        .flatten()
+        .map(|label| label.offset())
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, vec![20, 35]);
+
+    let report = miette::Report::new(diagnostics[0].clone()).with_source_code(source);
+    let output = format!(
+        "{:?}",
+        Render(
+            handler(ColorChoice::Never, DEFAULT_TAB_WIDTH),
+            report.as_ref()
+        )
+    );
+    let first = output.find("first declared here").unwrap();
+    let redeclared = output.find("redeclared here").unwrap();
+    assert!(first < redeclared);
+}
@@ -0,0 +1,82 @@
+use crate::{
+    ast::{find_node_at, ExpressionValue, Node, Position, Primitive, TypeValue},
+    check, parse,
+};
+
+#[test]
+fn parse_valid_program() {
+    let (module, diagnostics) = parse("fn main() {}");
+
+    assert!(module.is_some());
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn parse_invalid_program() {
+    let (module, diagnostics) = parse("fn main( {}");
+
+    assert!(module.is_none());
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn parse_error_inside_block() {
+    let (_, diagnostics) = parse("fn main() { let a = ; }");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "expected an expression, found `;`");
+    assert_eq!(
+        diagnostics[0].help.as_deref(),
+        Some("`;` is not an expression\nnote: while parsing block")
+    );
+    assert!(diagnostics[0].labels.is_some());
+}
+
+#[test]
+fn check_valid_program() {
+    let (modules, diagnostics) = check("fn main() { let a ~ i32 = 1; a }");
+
+    assert!(diagnostics.is_empty());
+    assert_eq!(modules.unwrap()[0].definitions.len(), 1);
+}
+
+#[test]
+fn check_invalid_program() {
+    let (modules, diagnostics) = check("fn main() { let _a ~ bool = 1; }");
+
+    assert!(modules.is_none());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "the value must match the declared type"
+    );
+    assert!(diagnostics[0].labels.is_some());
+}
+
+#[test]
+fn find_node_in_binary_operand() {
+    let source = "fn double(n ~ i32) -> i32 {\n  n * 2\n}";
+    let (modules, _) = check(source);
+    let modules = modules.unwrap();
+
+    let node = find_node_at(&modules, source, &Position { line: 2, column: 7 }).unwrap();
+    match node {
+        Node::Expression(expression) => assert!(matches!(
+            expression.value,
+            ExpressionValue::Primitive(Primitive::Integer(2))
+        )),
+        _ => panic!("expected the right operand, found {:?}", node),
+    }
+
+    let node = find_node_at(
+        &modules,
+        source,
+        &Position {
+            line: 1,
+            column: 27,
+        },
+    )
+    .unwrap();
+    assert!(matches!(node, Node::TypedExpression(_)));
+    assert_eq!(node.ty().unwrap().value, TypeValue::Integer);
+}
@@ -0,0 +1,58 @@
+use crate::ast::{FunctionHeader, Module, StatementValue};
+use crate::Result;
+use miette::{LabeledSpan, MietteDiagnostic};
+
+/// Finds the `main` function the program starts at. It may live in any of the modules, but
+/// there must be exactly one.
+pub fn entry_point<'a, 'ast, Expression>(
+    modules: &'a [Module<'ast, Expression>],
+) -> Result<&'a FunctionHeader<'ast>> {
+    let candidates = modules
+        .iter()
+        .flat_map(|module| {
+            module
+                .definitions
+                .iter()
+                .filter_map(move |statement| match &statement.value {
+                    StatementValue::Function { header, .. } if header.name == "main" => {
+                        Some((module, header))
+                    }
+                    _ => None,
+                })
+        })
+        .collect::<Vec<_>>();
+
+    match candidates.as_slice() {
+        [(_, header)] => Ok(header),
+        [] => Err(vec![MietteDiagnostic {
+            code: None,
+            severity: None,
+            url: None,
+            labels: None,
+            message: "no `main` function found".to_owned(),
+            help: Some("add a function, such as `fn main() {}`".to_owned()),
+        }]),
+        _ => Err(vec![MietteDiagnostic {
+            code: None,
+            severity: None,
+            url: None,
+            labels: Some(
+                candidates
+                    .iter()
+                    .map(|(module, header)| {
+                        LabeledSpan::at(header.span, format!("declared in `{}`", module.name))
+                    })
+                    .collect(),
+            ),
+            message: "multiple `main` functions found".to_owned(),
+            help: Some(format!(
+                "keep only one of the `main` functions in {}",
+                candidates
+                    .iter()
+                    .map(|(module, _)| format!("`{}`", module.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }]),
+    }
+}
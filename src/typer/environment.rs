@@ -1,10 +1,17 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
 
 use crate::ast::{Type, TypeValue};
 
 pub struct Environment<'env, 'ast> {
     parent: Option<&'env Environment<'env, 'ast>>,
     bindings: HashMap<Cow<'env, str>, Type<'ast>>,
+    /// The names of the functions declared in this scope, as opposed to values.
+    functions: HashSet<Cow<'env, str>>,
+    /// The names bound in this scope by the language rather than by the user, like `recurse`.
+    implicit: HashSet<Cow<'env, str>>,
 }
 
 pub enum EnvironmentType<'ast> {
@@ -17,6 +24,8 @@ impl<'env, 'ast> Environment<'env, 'ast> {
         Self {
             parent,
             bindings: HashMap::new(),
+            functions: HashSet::new(),
+            implicit: HashSet::new(),
         }
     }
 
@@ -35,10 +44,45 @@ impl<'env, 'ast> Environment<'env, 'ast> {
         }
     }
 
+    /// Declares a function in this scope. Fails with the existing binding when a function of
+    /// the same name is already declared in this same scope; shadowing a value, or a binding
+    /// from a parent scope, is fine.
+    pub fn declare_function(
+        &mut self,
+        name: Cow<'env, str>,
+        ty: Type<'ast>,
+    ) -> std::result::Result<(), Type<'ast>> {
+        if self.functions.contains(&name) {
+            return Err(self.bindings[&name].clone());
+        }
+
+        self.functions.insert(name.clone());
+        self.set(name, ty);
+        Ok(())
+    }
+
+    /// Binds a name the user didn't declare, such as `recurse` in a lambda.
+    pub fn set_implicit(&mut self, name: Cow<'env, str>, ty: Type<'ast>) {
+        self.implicit.insert(name.clone());
+        self.set(name, ty);
+    }
+
+    /// Whether the closest binding of `name` is one the user didn't declare.
+    pub fn is_implicit(&self, name: &str) -> bool {
+        match self.bindings.contains_key(name) {
+            true => self.implicit.contains(name),
+            false => self.parent.is_some_and(|p| p.is_implicit(name)),
+        }
+    }
+
     pub fn get(&self, name: &str) -> Option<&Type<'ast>> {
         self.bindings
             .get(name)
             .or_else(|| self.parent.and_then(|p| p.get(name)))
-            .map(|ty| ty.base_type())
+    }
+
+    pub fn get_type_alias(&self, name: &str) -> Option<&Type<'ast>> {
+        self.get(name)
+            .filter(|ty| matches!(ty.value, TypeValue::Alias(_, _)))
     }
 }
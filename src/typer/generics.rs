@@ -0,0 +1,147 @@
+use super::TypeChecker;
+use crate::ast::{Type, TypeValue};
+use miette::MietteDiagnostic;
+use std::{borrow::Cow, collections::HashMap};
+
+type Substitution<'ast> = HashMap<Cow<'ast, str>, Type<'ast>>;
+
+/// Type arguments are inferred from the arguments of a call to a generic function: in `id(5)`
+/// with `fn id<T>(x ~ T) -> T`, `T` is `i32`. The first argument a type parameter appears in
+/// decides it, and the other arguments must then match.
+impl<'ast> TypeChecker<'ast> {
+    pub(super) fn instantiate(
+        &mut self,
+        callee: &Type<'ast>,
+        parameters: &[Type<'ast>],
+        return_type: &Type<'ast>,
+        arguments: &[Type<'ast>],
+    ) -> (Vec<Type<'ast>>, Type<'ast>) {
+        let mut substitution = Substitution::new();
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            infer(parameter, argument, &mut substitution);
+        }
+
+        let mut unresolved = vec![];
+        generics(return_type, &mut unresolved);
+        unresolved.retain(|name| !substitution.contains_key(name));
+
+        for (i, name) in unresolved.iter().enumerate() {
+            if unresolved[..i].contains(name) {
+                continue;
+            }
+
+            self.errors.push(MietteDiagnostic {
+                code: None,
+                severity: None,
+                url: None,
+                labels: Some(callee.label("generic function call")),
+                help: Some(format!(
+                    "`{}` is only used in the return type, so it cannot be inferred from the arguments",
+                    name
+                )),
+                message: format!("cannot infer type parameter `{}`", name),
+            });
+        }
+
+        let parameters = parameters
+            .iter()
+            .map(|parameter| substitute(parameter, &substitution))
+            .collect();
+
+        (parameters, substitute(return_type, &substitution))
+    }
+}
+
+fn infer<'ast>(
+    parameter: &Type<'ast>,
+    argument: &Type<'ast>,
+    substitution: &mut Substitution<'ast>,
+) {
+    match (&parameter.base_type().value, &argument.base_type().value) {
+        (TypeValue::Generic(name), _) => {
+            substitution
+                .entry(name.clone())
+                .or_insert_with(|| argument.clone().concretize_default());
+        }
+        (TypeValue::Collection(parameter), TypeValue::Collection(argument))
+        | (TypeValue::Set(parameter), TypeValue::Set(argument)) => {
+            infer(parameter, argument, substitution)
+        }
+        (
+            TypeValue::Function {
+                parameters,
+                return_type,
+                ..
+            },
+            TypeValue::Function {
+                parameters: arguments,
+                return_type: argument_return_type,
+                ..
+            },
+        ) => {
+            for (parameter, argument) in parameters.iter().zip(arguments) {
+                infer(parameter, argument, substitution);
+            }
+            infer(return_type, argument_return_type, substitution);
+        }
+        _ => {}
+    }
+}
+
+fn substitute<'ast>(ty: &Type<'ast>, substitution: &Substitution<'ast>) -> Type<'ast> {
+    let mut names = vec![];
+    generics(ty, &mut names);
+    if names.is_empty() {
+        return ty.clone();
+    }
+
+    match &ty.base_type().value {
+        TypeValue::Generic(name) => match substitution.get(name) {
+            Some(argument) => argument.clone().span(ty.span),
+            None => ty.clone(),
+        },
+        TypeValue::Collection(element) => {
+            Type::collection(ty.span, substitute(element, substitution))
+        }
+        TypeValue::Set(element) => Type::set(ty.span, substitute(element, substitution)),
+        TypeValue::Function {
+            parameters,
+            parameter_names,
+            return_type,
+        } => {
+            let substituted = Type::function(
+                ty.span,
+                parameters
+                    .iter()
+                    .map(|parameter| substitute(parameter, substitution))
+                    .collect(),
+                substitute(return_type, substitution),
+            );
+
+            match parameter_names {
+                Some(names) => substituted.parameter_names(names.clone()),
+                None => substituted,
+            }
+        }
+        _ => ty.clone(),
+    }
+}
+
+/// Collects the names of the type parameters used in `ty`, in order of appearance.
+fn generics<'ast>(ty: &Type<'ast>, names: &mut Vec<Cow<'ast, str>>) {
+    match &ty.base_type().value {
+        TypeValue::Generic(name) => names.push(name.clone()),
+        TypeValue::Collection(element) | TypeValue::Set(element) => generics(element, names),
+        TypeValue::Function {
+            parameters,
+            return_type,
+            ..
+        } => {
+            for parameter in parameters {
+                generics(parameter, names);
+            }
+            generics(return_type, names);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,204 @@
+use super::{environment::Environment, TypeChecker};
+use crate::ast::{Expression, ExpressionValue, Primitive, Statement, StatementValue, Type};
+use crate::Result;
+use miette::{MietteDiagnostic, SourceSpan};
+use std::collections::HashMap;
+
+/// Declares the functions that are built into the language.
+///
+/// - `len(s ~ str) -> i64` is the length of a string in bytes.
+/// - `byte_at(s ~ str, i ~ i64) -> char` is the byte at index `i` of a string. It returns a
+///   `char` as there is no `byte` type yet. Indexing past the end of the string is undefined
+///   behaviour for now.
+pub fn declare(environment: &mut Environment<'_, '_>) {
+    let span = SourceSpan::new(0.into(), 0);
+
+    environment.set(
+        "len".into(),
+        Type::function(span, vec![Type::string(span)], Type::long(span))
+            .parameter_names(vec!["s".into()]),
+    );
+
+    environment.set(
+        "byte_at".into(),
+        Type::function(
+            span,
+            vec![Type::string(span), Type::long(span)],
+            Type::character(span),
+        )
+        .parameter_names(vec!["s".into(), "i".into()]),
+    );
+}
+
+/// Intrinsics that are evaluated while type checking. Calls to them are replaced in the typed
+/// tree with their result, so nothing of them is left for code generation.
+///
+/// - `typeof(value)` is the inferred type of `value`, as a string such as `"i32"`.
+/// - `static_assert_type(value, "i32")` fails type checking when the inferred type of `value`
+///   is not the given one. It evaluates to nothing.
+impl<'ast> TypeChecker<'ast> {
+    pub(super) fn compile_time_intrinsic<'env>(
+        &mut self,
+        expression: &Expression<'ast>,
+        environment: &Environment<'env, 'ast>,
+    ) -> Option<Result<Type<'ast>>> {
+        let (name, arguments) = match &expression.value {
+            ExpressionValue::Call { callee, arguments } => match &callee.value {
+                ExpressionValue::Primitive(Primitive::Identifier(name))
+                    if environment.get(name).is_none() =>
+                {
+                    (name, arguments)
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let result = match (name.as_ref(), arguments.as_slice()) {
+            ("typeof", [value]) => self.type_of(value, environment).map(|ty| {
+                let name = ty.concretize_default().verbose();
+                self.evaluated
+                    .insert(expression.span, Primitive::String(name.into()));
+
+                Type::string(expression.span)
+            }),
+            ("static_assert_type", [value, expected]) => {
+                let expected = match &expected.value {
+                    ExpressionValue::Primitive(Primitive::String(expected)) => expected,
+                    _ => {
+                        return Some(Err(vec![MietteDiagnostic {
+                            code: None,
+                            severity: None,
+                            url: None,
+                            labels: Some(vec![expected.label("expected a string")]),
+                            help: Some("write the type as a string, such as \"i32\"".into()),
+                            message: "the expected type must be a string literal".to_owned(),
+                        }]))
+                    }
+                };
+
+                self.type_of(value, environment).map(|ty| {
+                    let ty = ty.concretize_default();
+                    let actual = ty.verbose();
+
+                    if actual != *expected {
+                        self.errors.push(MietteDiagnostic {
+                            code: None,
+                            severity: None,
+                            url: None,
+                            labels: Some(vec![value.label(format!("{}", ty))]),
+                            help: Some(format!("expected `{}`, found `{}`", expected, actual)),
+                            message: "type assertion failed".to_owned(),
+                        });
+                    }
+
+                    self.evaluated.insert(expression.span, Primitive::Unit);
+                    Type::unit(expression.span)
+                })
+            }
+            ("typeof" | "static_assert_type", _) => Err(vec![MietteDiagnostic {
+                code: None,
+                severity: None,
+                url: None,
+                labels: Some(vec![expression.label("intrinsic call")]),
+                help: Some(match name.as_ref() {
+                    "typeof" => "`typeof` expects one value".into(),
+                    _ => "`static_assert_type` expects a value and a type name".into(),
+                }),
+                message: "incorrect number of arguments".to_owned(),
+            }]),
+            _ => return None,
+        };
+
+        Some(result)
+    }
+}
+
+/// Replaces the calls to compile-time intrinsics in `expression` with their results.
+pub fn replace_evaluated<'ast>(
+    expression: &mut Expression<'ast>,
+    evaluated: &HashMap<SourceSpan, Primitive<'ast>>,
+) {
+    if let Some(result) = evaluated.get(&expression.span) {
+        if matches!(expression.value, ExpressionValue::Call { .. }) {
+            expression.value = ExpressionValue::Primitive(result.clone());
+            return;
+        }
+    }
+
+    match &mut expression.value {
+        ExpressionValue::Primitive(_) | ExpressionValue::Path(_) => {}
+        ExpressionValue::Binary { left, right, .. } => {
+            replace_evaluated(left, evaluated);
+            replace_evaluated(right, evaluated);
+        }
+        ExpressionValue::Unary { operand, .. } => replace_evaluated(operand, evaluated),
+        ExpressionValue::Group(expression) => replace_evaluated(expression, evaluated),
+        ExpressionValue::Block {
+            statements,
+            return_value,
+        } => {
+            for statement in statements {
+                replace_evaluated_in_statement(statement, evaluated);
+            }
+            replace_evaluated(return_value, evaluated);
+        }
+        ExpressionValue::Conditional {
+            condition,
+            truthy,
+            falsy,
+        } => {
+            replace_evaluated(condition, evaluated);
+            replace_evaluated(truthy, evaluated);
+            replace_evaluated(falsy, evaluated);
+        }
+        ExpressionValue::Call { callee, arguments } => {
+            replace_evaluated(callee, evaluated);
+            for argument in arguments {
+                replace_evaluated(argument, evaluated);
+            }
+        }
+        ExpressionValue::Lambda(lambda) => replace_evaluated(&mut lambda.body, evaluated),
+        ExpressionValue::Interpolation(parts) => {
+            for part in parts {
+                replace_evaluated(part, evaluated);
+            }
+        }
+    }
+}
+
+fn replace_evaluated_in_statement<'ast>(
+    statement: &mut Statement<'ast, Expression<'ast>>,
+    evaluated: &HashMap<SourceSpan, Primitive<'ast>>,
+) {
+    match &mut statement.value {
+        StatementValue::Block(statements) => {
+            for statement in statements {
+                replace_evaluated_in_statement(statement, evaluated);
+            }
+        }
+        StatementValue::Expression(expression)
+        | StatementValue::Return(expression)
+        | StatementValue::Assignment {
+            value: expression, ..
+        }
+        | StatementValue::Function {
+            body: expression, ..
+        } => replace_evaluated(expression, evaluated),
+        StatementValue::Conditional {
+            condition,
+            truthy,
+            falsy,
+        } => {
+            replace_evaluated(condition, evaluated);
+            replace_evaluated_in_statement(truthy, evaluated);
+            if let Some(falsy) = falsy {
+                replace_evaluated_in_statement(falsy, evaluated);
+            }
+        }
+        StatementValue::Struct { .. }
+        | StatementValue::Enum { .. }
+        | StatementValue::Trait { .. }
+        | StatementValue::TypeAlias { .. } => {}
+    }
+}
@@ -1,28 +1,93 @@
 use crate::ast::{
-    BinaryOperator, CombineSpan, Expression, ExpressionValue, Module, Primitive, Statement,
-    StatementValue, Type, TypeValue, TypedExpression, TypedStatement,
+    Attribute, BinaryOperator, CombineSpan, Expression, ExpressionValue, FunctionHeader,
+    Identifier, Module, Primitive, Statement, StatementValue, Type, TypeValue, TypedExpression,
+    TypedStatement, UnaryOperator,
 };
+use crate::parser::DEFAULT_MAX_DEPTH;
 use crate::Result;
+pub use entry::entry_point;
 use environment::Environment;
-use miette::{MietteDiagnostic, SourceSpan};
+use miette::{LabeledSpan, MietteDiagnostic, Severity, SourceSpan};
+use std::{borrow::Cow, collections::HashMap};
 
+const NUMERIC_TYPES: &[TypeValue<'static>] = &[
+    TypeValue::Integer,
+    TypeValue::Long,
+    TypeValue::IntegerLiteral,
+    TypeValue::Decimal,
+];
+
+/// The types whose values can be compared with `==` and `!=`.
+const EQUATABLE_TYPES: &[TypeValue<'static>] = &[
+    TypeValue::Boolean,
+    TypeValue::Integer,
+    TypeValue::Long,
+    TypeValue::IntegerLiteral,
+    TypeValue::Decimal,
+    TypeValue::Character,
+    TypeValue::String,
+];
+
+const KNOWN_ATTRIBUTES: &[&str] = &["inline", "no_mangle", "test"];
+
+mod entry;
 pub mod environment;
+mod generics;
+mod intrinsics;
 #[cfg(test)]
 mod tests;
-pub struct TypeChecker {
+mod usage;
+pub struct TypeChecker<'ast> {
     errors: Vec<MietteDiagnostic>,
+    warnings: Vec<MietteDiagnostic>,
+    /// Results of compile-time intrinsic calls, by the span of the call.
+    evaluated: HashMap<SourceSpan, Primitive<'ast>>,
+    /// Parameter and return types of the functions declared ahead of their bodies, by the span
+    /// of their header.
+    signatures: HashMap<SourceSpan, Signature<'ast>>,
+    depth: usize,
+    max_depth: usize,
 }
 
-impl<'ast> TypeChecker {
+type Signature<'ast> = (Vec<Type<'ast>>, Option<Type<'ast>>);
+
+impl Default for TypeChecker<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'ast> TypeChecker<'ast> {
     pub fn new() -> Self {
-        Self { errors: vec![] }
+        Self {
+            errors: vec![],
+            warnings: vec![],
+            evaluated: HashMap::new(),
+            signatures: HashMap::new(),
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Warnings found by the last call to [`TypeChecker::type_check`]. They do not fail type
+    /// checking.
+    pub fn warnings(&self) -> &[MietteDiagnostic] {
+        &self.warnings
     }
 
     pub fn type_check(
         &mut self,
         modules: Vec<Module<'ast, Expression<'ast>>>,
     ) -> Result<Vec<Module<'ast, TypedExpression<'ast>>>> {
-        let mut environment = Environment::new(None);
+        let mut intrinsics = Environment::new(None);
+        intrinsics::declare(&mut intrinsics);
+
+        let mut environment = Environment::new(Some(&intrinsics));
 
         let typed_modules = modules
             .into_iter()
@@ -41,12 +106,51 @@ impl<'ast> TypeChecker {
         module: Module<'ast, Expression<'ast>>,
         environment: &mut Environment<'env, 'ast>,
     ) -> Module<'ast, TypedExpression<'ast>> {
-        let typed_statements = module
-            .definitions
+        self.warnings.extend(usage::unused_variables(&module));
+
+        for statement in &module.definitions {
+            match &statement.value {
+                StatementValue::Function { header, .. } => {
+                    self.expect_known_attributes(&header.attributes)
+                }
+                StatementValue::Struct { attributes, .. } => {
+                    self.expect_known_attributes(attributes)
+                }
+                _ => {}
+            }
+        }
+
+        // Type aliases and function signatures are declared first, so functions can call the
+        // ones declared after them, including each other
+        let mut typed_statements = self.declare_type_aliases(&module.definitions, environment);
+        for stmt in &module.definitions {
+            if let StatementValue::Function { header, .. } = &stmt.value {
+                if header.explicit_return_type.is_some() {
+                    let signature = self.declare_signature(header, environment);
+                    self.signatures.insert(header.span, signature);
+                }
+            }
+        }
+
+        for (stmt, typed) in module.definitions.iter().zip(&mut typed_statements) {
+            if typed.is_none() {
+                *typed = Some(self.type_check_statement(stmt, environment));
+            }
+        }
+
+        let typed_statements = typed_statements
             .into_iter()
-            .map(|stmt| self.type_check_statement(&stmt, environment))
             .flatten()
-            .collect();
+            .flatten()
+            .collect::<Vec<_>>();
+
+        for statement in &typed_statements {
+            if let StatementValue::Function { header, body } = &statement.value {
+                if header.name == "main" {
+                    self.expect_valid_main(header, body);
+                }
+            }
+        }
 
         Module {
             definitions: typed_statements,
@@ -67,14 +171,76 @@ impl<'ast> TypeChecker {
                     span: statement.span,
                 })
             }
+            StatementValue::Assignment {
+                name,
+                explicit_type,
+                value,
+            } => {
+                let ty = match self.type_of(value, environment) {
+                    Ok(ty) => ty,
+                    Err(err) => {
+                        self.errors.extend(err);
+                        return None;
+                    }
+                };
+
+                let ty = match explicit_type {
+                    Some(explicit_type) => {
+                        let explicit_type = &self.resolve_type(explicit_type, environment);
+
+                        self.expect_match(
+                            explicit_type,
+                            &ty,
+                            "the value must match the declared type".into(),
+                        );
+                        self.expect_integer_literal_fits(value, explicit_type);
+
+                        explicit_type.clone().span(ty.span)
+                    }
+                    None => widen_integer_literal(value, ty).concretize_default(),
+                };
+
+                environment.set(name.clone(), ty.clone());
+
+                Some(TypedStatement {
+                    value: StatementValue::Assignment {
+                        name: name.clone(),
+                        explicit_type: explicit_type.clone(),
+                        value: self.to_typed(value, ty),
+                    },
+                    span: statement.span,
+                })
+            }
             StatementValue::Function { header, body } => {
-                let mut environment = Environment::new(Some(environment));
+                let (parameters, explicit_return_type) = match self.signatures.get(&header.span) {
+                    Some(signature) => signature.clone(),
+                    None => self.declare_signature(header, environment),
+                };
 
-                for parameter in &header.parameters {
-                    environment.set(parameter.name.clone(), parameter.explicit_type.clone());
-                }
+                let body = {
+                    let mut environment =
+                        Self::type_parameter_scope(&header.type_parameters, environment);
+
+                    for (parameter, ty) in header.parameters.iter().zip(&parameters) {
+                        environment.set(parameter.name.clone(), ty.clone());
+                    }
 
-                let body = self.type_check_expression(body, &environment)?;
+                    self.type_check_expression(body, &environment)?
+                };
+
+                match &explicit_return_type {
+                    Some(return_type) => self.expect_match(
+                        return_type,
+                        &body.ty,
+                        "the body must match the declared return type".into(),
+                    ),
+                    None => {
+                        let return_type = body.ty.clone().concretize_default();
+                        let ty = Type::function(header.span, parameters, return_type)
+                            .parameter_names(header.parameter_names());
+                        self.declare_function(header, ty, environment);
+                    }
+                }
 
                 Some(TypedStatement {
                     value: StatementValue::Function {
@@ -84,7 +250,101 @@ impl<'ast> TypeChecker {
                     span: statement.span,
                 })
             }
-            _ => todo!("type_check_statement: {}", statement),
+            StatementValue::TypeAlias {
+                name,
+                explicit_type,
+            } => {
+                let explicit_type = self.resolve_type(explicit_type, environment);
+
+                environment.set(
+                    name.clone(),
+                    Type::alias(statement.span, name.clone(), explicit_type.clone()),
+                );
+
+                Some(TypedStatement {
+                    value: StatementValue::TypeAlias {
+                        name: name.clone(),
+                        explicit_type,
+                    },
+                    span: statement.span,
+                })
+            }
+            StatementValue::Conditional {
+                condition,
+                truthy,
+                falsy,
+            } => {
+                let condition = self.type_check_expression(condition, environment)?;
+                self.expect_type(
+                    &condition.ty,
+                    TypeValue::Boolean,
+                    "the condition must be boolean".into(),
+                );
+
+                let truthy = {
+                    let mut environment = Environment::new(Some(environment));
+                    self.type_check_statement(truthy, &mut environment)?
+                };
+
+                let falsy = match falsy {
+                    Some(falsy) => {
+                        let mut environment = Environment::new(Some(environment));
+                        Some(self.type_check_statement(falsy, &mut environment)?)
+                    }
+                    None => None,
+                };
+
+                if falsy.is_none() {
+                    if let StatementValue::Expression(value) = &truthy.value {
+                        if value.ty.base_type().value != TypeValue::Unit {
+                            self.errors.push(MietteDiagnostic {
+                                code: None,
+                                severity: None,
+                                url: None,
+                                labels: Some(value.ty.label(format!("{}", value.ty))),
+                                help: Some(
+                                    "add an else branch, or discard the value with a semicolon"
+                                        .into(),
+                                ),
+                                message: "an if without an else cannot produce a value".to_owned(),
+                            });
+                        }
+                    }
+                }
+
+                Some(TypedStatement {
+                    value: StatementValue::Conditional {
+                        condition: Box::new(condition),
+                        truthy: Box::new(truthy),
+                        falsy: falsy.map(Box::new),
+                    },
+                    span: statement.span,
+                })
+            }
+            StatementValue::Block(_)
+            | StatementValue::Return(_)
+            | StatementValue::Struct { .. }
+            | StatementValue::Enum { .. }
+            | StatementValue::Trait { .. } => {
+                let construct = match &statement.value {
+                    StatementValue::Block(_) => "block statements",
+                    StatementValue::Return(_) => "return statements",
+                    StatementValue::Struct { .. } => "structs",
+                    StatementValue::Enum { .. } => "enums",
+                    _ => "traits",
+                };
+
+                self.errors.push(MietteDiagnostic {
+                    code: None,
+                    severity: None,
+                    url: None,
+                    labels: Some(vec![LabeledSpan::at(statement.span, construct)]),
+                    help: Some(format!("{} cannot be type checked yet", construct)),
+                    message: "feature not yet implemented".to_owned(),
+                });
+
+                None
+            }
         }
     }
 
@@ -93,8 +353,8 @@ impl<'ast> TypeChecker {
         expression: &Expression<'ast>,
         environment: &'env Environment<'env, 'ast>,
     ) -> Option<TypedExpression<'ast>> {
-        match self.type_of(&expression, environment) {
-            Ok(ty) => Some(expression.clone().to_typed(ty)),
+        match self.type_of(expression, environment) {
+            Ok(ty) => Some(self.to_typed(expression, ty)),
             Err(err) => {
                 self.errors.extend(err);
                 None
@@ -102,14 +362,53 @@ impl<'ast> TypeChecker {
         }
     }
 
+    /// Annotates `expression` with its type, with compile-time intrinsics replaced by their
+    /// results.
+    fn to_typed(&self, expression: &Expression<'ast>, ty: Type<'ast>) -> TypedExpression<'ast> {
+        let mut expression = expression.clone();
+        if !self.evaluated.is_empty() {
+            intrinsics::replace_evaluated(&mut expression, &self.evaluated);
+        }
+
+        expression.to_typed(ty)
+    }
+
     fn type_of<'env>(
         &mut self,
         expression: &Expression<'ast>,
         environment: &Environment<'env, 'ast>,
+    ) -> Result<Type<'ast>> {
+        if self.depth >= self.max_depth {
+            return Err(vec![MietteDiagnostic {
+                code: None,
+                severity: None,
+                url: None,
+                labels: Some(vec![expression.label("nested too deeply")]),
+                help: Some(format!(
+                    "expressions can be nested at most {} levels deep",
+                    self.max_depth
+                )),
+                message: "expression nesting too deep".to_owned(),
+            }]);
+        }
+
+        self.depth += 1;
+        let ty = self.type_of_nested(expression, environment);
+        self.depth -= 1;
+
+        ty
+    }
+
+    fn type_of_nested<'env>(
+        &mut self,
+        expression: &Expression<'ast>,
+        environment: &Environment<'env, 'ast>,
     ) -> Result<Type<'ast>> {
         match &expression.value {
             ExpressionValue::Primitive(primitive) => match primitive {
-                Primitive::Integer(_) => Ok(Type::integer(expression.span)),
+                Primitive::Integer(_) => Ok(Type::integer_literal(expression.span)),
+                Primitive::Int32(_) => Ok(Type::integer(expression.span)),
+                Primitive::Int64(_) => Ok(Type::long(expression.span)),
                 Primitive::Decimal(_) => Ok(Type::decimal(expression.span)),
                 Primitive::Boolean(_) => Ok(Type::boolean(expression.span)),
                 Primitive::String(_) => Ok(Type::string(expression.span)),
@@ -130,7 +429,13 @@ impl<'ast> TypeChecker {
                 Primitive::Character(_) => Ok(Type::character(expression.span)),
                 Primitive::Unit => Ok(Type::unit(expression.span)),
             },
-            ExpressionValue::Group(expr) => self.type_of(expr, environment),
+            // A block is a group too, but its type stays at the value it evaluates to
+            ExpressionValue::Group(expr) if matches!(expr.value, ExpressionValue::Block { .. }) => {
+                self.type_of(expr, environment)
+            }
+            ExpressionValue::Group(expr) => self
+                .type_of(expr, environment)
+                .map(|ty| ty.span(expression.span)),
             ExpressionValue::Block {
                 statements,
                 return_value,
@@ -139,7 +444,13 @@ impl<'ast> TypeChecker {
                 let mut environment = Environment::new(Some(environment));
 
                 for stmt in statements {
-                    self.type_check_statement(stmt, &mut environment);
+                    if let Some(Statement {
+                        value: StatementValue::Expression(value),
+                        ..
+                    }) = self.type_check_statement(stmt, &mut environment)
+                    {
+                        self.expect_discardable(&value);
+                    }
                 }
 
                 self.type_of(return_value, &environment)
@@ -161,9 +472,8 @@ impl<'ast> TypeChecker {
                 if operator.is_comparison() {
                     Ok(Type::boolean(expression.span))
                 } else {
-                    Ok(left
-                        .clone()
-                        .span(SourceSpan::combine(vec![left.span, right.span])))
+                    let span = SourceSpan::combine(vec![left.span, right.span]);
+                    Ok(left.concretize(&right).span(span))
                 }
             }
             ExpressionValue::Unary {
@@ -190,15 +500,20 @@ impl<'ast> TypeChecker {
                     "truthy and falsy branches must be of the same type".into(),
                 );
 
-                Ok(truthy)
+                Ok(truthy.concretize(&falsy))
             }
             ExpressionValue::Call { callee, arguments } => {
+                if let Some(result) = self.compile_time_intrinsic(expression, environment) {
+                    return result;
+                }
+
                 let callee = self.type_of(callee, environment)?;
 
-                match callee.clone().value {
+                match callee.base_type().clone().value {
                     TypeValue::Function {
                         parameters,
                         return_type,
+                        ..
                     } => {
                         if parameters.len() != arguments.len() {
                             return Err(vec![MietteDiagnostic {
@@ -207,7 +522,8 @@ impl<'ast> TypeChecker {
                                 url: None,
                                 labels: Some(callee.label("function call")),
                                 help: Some(format!(
-                                    "expected {} arguments, but found {}",
+                                    "{} expects {} arguments, but found {}",
+                                    callee.base_type().verbose(),
                                     parameters.len(),
                                     arguments.len()
                                 )),
@@ -215,14 +531,28 @@ impl<'ast> TypeChecker {
                             }]);
                         }
 
-                        for (parameter, argument) in parameters.iter().zip(arguments) {
-                            let argument = self.type_of(argument, environment)?;
+                        let argument_types = arguments
+                            .iter()
+                            .map(|argument| self.type_of(argument, environment))
+                            .collect::<Result<Vec<_>>>()?;
 
+                        let inferred_types = arguments
+                            .iter()
+                            .zip(&argument_types)
+                            .map(|(argument, ty)| widen_integer_literal(argument, ty.clone()))
+                            .collect::<Vec<_>>();
+                        let (parameters, return_type) =
+                            self.instantiate(&callee, &parameters, &return_type, &inferred_types);
+
+                        for ((parameter, argument), argument_type) in
+                            parameters.iter().zip(arguments).zip(argument_types)
+                        {
                             self.expect_match(
                                 parameter,
-                                &argument,
+                                &argument_type,
                                 "argument and parameter must match".into(),
                             );
+                            self.expect_integer_literal_fits(argument, parameter);
                         }
 
                         Ok((return_type.span(callee.span)).clone())
@@ -237,25 +567,397 @@ impl<'ast> TypeChecker {
                     }]),
                 }
             }
+            ExpressionValue::Interpolation(parts) => {
+                for part in parts {
+                    let ty = self.type_of(part, environment)?;
+
+                    if !matches!(
+                        ty.base_type().value,
+                        TypeValue::Boolean
+                            | TypeValue::Integer
+                            | TypeValue::Long
+                            | TypeValue::IntegerLiteral
+                            | TypeValue::Decimal
+                            | TypeValue::Character
+                            | TypeValue::String
+                    ) {
+                        self.errors.push(MietteDiagnostic {
+                            code: None,
+                            severity: None,
+                            url: None,
+                            labels: Some(vec![part.label(format!("{}", ty))]),
+                            help: Some(
+                                "only booleans, numbers, characters and strings can be embedded in a string"
+                                    .into(),
+                            ),
+                            message: "cannot interpolate this value".to_owned(),
+                        });
+                    }
+                }
+
+                Ok(Type::string(expression.span))
+            }
+            // There are no modules to import yet, so the first segment never resolves
+            ExpressionValue::Path(path) => Err(vec![MietteDiagnostic {
+                code: None,
+                severity: None,
+                url: None,
+                labels: Some(vec![LabeledSpan::at(
+                    path.segments[0].span,
+                    "unknown module",
+                )]),
+                help: Some(format!(
+                    "`{}` cannot be resolved, modules cannot be imported yet",
+                    path
+                )),
+                message: format!("unknown module `{}`", path.segments[0].name),
+            }]),
             ExpressionValue::Lambda(lambda) => {
                 let mut environment = Environment::new(Some(environment));
 
-                for parameter in &lambda.parameters {
-                    environment.set(parameter.name.clone(), parameter.explicit_type.clone());
+                let parameters = lambda
+                    .parameters
+                    .iter()
+                    .map(|p| self.resolve_type(&p.explicit_type, &environment))
+                    .collect::<Vec<_>>();
+
+                for (parameter, ty) in lambda.parameters.iter().zip(&parameters) {
+                    environment.set(parameter.name.clone(), ty.clone());
                 }
 
+                // A lambda with a declared return type can call itself through `recurse`, unless
+                // the user declared a `recurse` of their own, which it would otherwise shadow
+                let explicit_return_type = match &lambda.explicit_return_type {
+                    Some(explicit_return_type)
+                        if environment.get("recurse").is_some()
+                            && !environment.is_implicit("recurse") =>
+                    {
+                        self.errors.push(MietteDiagnostic {
+                            code: None,
+                            severity: None,
+                            url: None,
+                            labels: Some(vec![LabeledSpan::at(
+                                expression.span,
+                                "this lambda binds `recurse` to itself",
+                            )]),
+                            help: Some(
+                                "rename the variable, a lambda with a declared return type binds `recurse` to itself"
+                                    .into(),
+                            ),
+                            message: "`recurse` is already declared".into(),
+                        });
+
+                        Some(self.resolve_type(explicit_return_type, &environment))
+                    }
+                    Some(explicit_return_type) => {
+                        let explicit_return_type =
+                            self.resolve_type(explicit_return_type, &environment);
+
+                        environment.set_implicit(
+                            "recurse".into(),
+                            Type::function(
+                                expression.span,
+                                parameters.clone(),
+                                explicit_return_type.clone(),
+                            )
+                            .parameter_names(lambda.parameter_names()),
+                        );
+
+                        Some(explicit_return_type)
+                    }
+                    None => None,
+                };
+
                 let body = self.type_of(&lambda.body, &environment)?;
 
-                Ok(Type::function(
-                    expression.span,
-                    lambda
+                let return_type = match explicit_return_type {
+                    Some(explicit_return_type) => {
+                        self.expect_match(
+                            &explicit_return_type,
+                            &body,
+                            "the body must match the declared return type".into(),
+                        );
+
+                        explicit_return_type
+                    }
+                    None => body,
+                };
+
+                Ok(Type::function(expression.span, parameters, return_type)
+                    .parameter_names(lambda.parameter_names()))
+            }
+        }
+    }
+
+    /// Resolves the parameter and return types of a function. A function with a declared return
+    /// type is declared right away, so it can be called from its own body.
+    fn declare_signature(
+        &mut self,
+        header: &FunctionHeader<'ast>,
+        environment: &mut Environment<'_, 'ast>,
+    ) -> Signature<'ast> {
+        let (parameters, explicit_return_type) = {
+            let scope = Self::type_parameter_scope(&header.type_parameters, environment);
+
+            let parameters = header
+                .parameters
+                .iter()
+                .map(|p| self.resolve_type(&p.explicit_type, &scope))
+                .collect::<Vec<_>>();
+
+            let explicit_return_type = header
+                .explicit_return_type
+                .as_ref()
+                .map(|ty| self.resolve_type(ty, &scope));
+
+            (parameters, explicit_return_type)
+        };
+
+        if let Some(return_type) = &explicit_return_type {
+            let ty = Type::function(header.span, parameters.clone(), return_type.clone())
+                .parameter_names(header.parameter_names());
+            self.declare_function(header, ty, environment);
+        }
+
+        (parameters, explicit_return_type)
+    }
+
+    /// A scope in which the type parameters of a generic declaration are known types.
+    fn type_parameter_scope<'env>(
+        type_parameters: &[Identifier<'ast>],
+        environment: &'env Environment<'env, 'ast>,
+    ) -> Environment<'env, 'ast> {
+        let mut scope = Environment::new(Some(environment));
+
+        for type_parameter in type_parameters {
+            let name = type_parameter.name.clone();
+            let ty = Type::generic(type_parameter.span, name.clone());
+            scope.set(name.clone(), Type::alias(type_parameter.span, name, ty));
+        }
+
+        scope
+    }
+
+    /// Unknown attributes are kept for tooling to use, but are likely a typo.
+    fn expect_known_attributes(&mut self, attributes: &[Attribute<'ast>]) {
+        for attribute in attributes {
+            if KNOWN_ATTRIBUTES.contains(&attribute.name.name.as_ref()) {
+                continue;
+            }
+
+            self.warnings.push(MietteDiagnostic {
+                code: None,
+                severity: Some(Severity::Warning),
+                url: None,
+                labels: Some(vec![LabeledSpan::at(
+                    attribute.name.span,
+                    "unknown attribute",
+                )]),
+                help: Some(format!(
+                    "the known attributes are {}",
+                    KNOWN_ATTRIBUTES
+                        .iter()
+                        .map(|name| format!("`{}`", name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+                message: format!("unknown attribute `{}`", attribute.name.name),
+            });
+        }
+    }
+
+    /// The value returned from `main` becomes the exit code of the program, so it can only
+    /// return nothing or an `i32`.
+    fn expect_valid_main(&mut self, header: &FunctionHeader<'ast>, body: &TypedExpression<'ast>) {
+        if !header.parameters.is_empty() {
+            self.errors.push(MietteDiagnostic {
+                code: None,
+                severity: None,
+                url: None,
+                labels: Some(
+                    header
                         .parameters
                         .iter()
-                        .map(|p| p.explicit_type.clone())
+                        .map(|parameter| LabeledSpan::at(parameter.span, "parameter"))
                         .collect(),
-                    body,
-                ))
+                ),
+                help: Some("`main` is called without arguments, remove its parameters".into()),
+                message: "`main` cannot take parameters".to_owned(),
+            });
+        }
+
+        let return_type = match self.signatures.get(&header.span) {
+            Some((_, Some(return_type))) => return_type.clone(),
+            _ => body.ty.clone(),
+        };
+
+        match return_type.base_type().value {
+            TypeValue::Unit | TypeValue::Integer => return,
+            TypeValue::IntegerLiteral => {
+                // The exit code is an `i32`, however wide the literal is
+                let mut result = Expression {
+                    value: body.value.clone(),
+                    span: body.span,
+                };
+                while let ExpressionValue::Group(inner)
+                | ExpressionValue::Block {
+                    return_value: inner,
+                    ..
+                } = result.value
+                {
+                    result = *inner;
+                }
+
+                self.expect_integer_literal_fits(&result, &Type::integer(return_type.span));
+                return;
             }
+            _ => {}
+        }
+
+        self.errors.push(MietteDiagnostic {
+            code: None,
+            severity: None,
+            url: None,
+            labels: Some(return_type.label(format!("{}", return_type))),
+            help: Some("return nothing, or an `i32` exit code".into()),
+            message: "`main` must return nothing or an `i32`".to_owned(),
+        });
+    }
+
+    fn declare_function(
+        &mut self,
+        header: &FunctionHeader<'ast>,
+        ty: Type<'ast>,
+        environment: &mut Environment<'_, 'ast>,
+    ) {
+        if let Err(existing) = environment.declare_function(header.name.clone(), ty) {
+            self.errors.push(MietteDiagnostic {
+                code: None,
+                severity: None,
+                url: None,
+                labels: Some(vec![
+                    LabeledSpan::at(header.span, "redeclared here"),
+                    LabeledSpan::at(existing.span, "first declared here"),
+                ]),
+                help: Some(format!(
+                    "rename one of the declarations of `{}`, or move one into a nested scope",
+                    header.name
+                )),
+                message: format!("`{}` is already declared", header.name),
+            });
+        }
+    }
+
+    /// Declares the type aliases of a module in dependency order, so an alias can refer to one
+    /// declared after it. Aliases that refer to each other can never be resolved and are reported
+    /// instead.
+    fn declare_type_aliases(
+        &mut self,
+        definitions: &[Statement<'ast, Expression<'ast>>],
+        environment: &mut Environment<'_, 'ast>,
+    ) -> Vec<Option<Option<TypedStatement<'ast>>>> {
+        let mut typed_statements = definitions.iter().map(|_| None).collect::<Vec<_>>();
+
+        let aliases = definitions
+            .iter()
+            .filter_map(|stmt| match &stmt.value {
+                StatementValue::TypeAlias { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        loop {
+            let mut progressed = false;
+
+            for (stmt, typed) in definitions.iter().zip(&mut typed_statements) {
+                let StatementValue::TypeAlias { explicit_type, .. } = &stmt.value else {
+                    continue;
+                };
+                if typed.is_some() {
+                    continue;
+                }
+
+                let mut symbols = vec![];
+                type_symbols(explicit_type, &mut symbols);
+                let pending = symbols.iter().any(|symbol| {
+                    aliases.contains(symbol) && environment.get_type_alias(symbol).is_none()
+                });
+
+                if !pending {
+                    *typed = Some(self.type_check_statement(stmt, environment));
+                    progressed = true;
+                }
+            }
+
+            if !progressed {
+                break;
+            }
+        }
+
+        for (stmt, typed) in definitions.iter().zip(&mut typed_statements) {
+            if let StatementValue::TypeAlias { name, .. } = &stmt.value {
+                if typed.is_none() {
+                    self.errors.push(MietteDiagnostic {
+                        code: None,
+                        severity: None,
+                        url: None,
+                        labels: Some(vec![stmt.label("recursive type alias")]),
+                        help: Some(format!(
+                            "`{}` refers to itself through other type aliases, so it never resolves to a type",
+                            name
+                        )),
+                        message: format!("type alias `{}` is recursive", name),
+                    });
+                    *typed = Some(None);
+                }
+            }
+        }
+
+        typed_statements
+    }
+
+    /// Replaces named types with the type alias they refer to.
+    fn resolve_type(&mut self, ty: &Type<'ast>, environment: &Environment<'_, 'ast>) -> Type<'ast> {
+        match &ty.value {
+            TypeValue::Symbol(name) => match environment.get_type_alias(name) {
+                Some(alias) => alias.clone().span(ty.span),
+                None => {
+                    self.errors.push(MietteDiagnostic {
+                        code: None,
+                        severity: None,
+                        url: None,
+                        labels: Some(ty.label("unknown type")),
+                        help: Some(format!("{} is not declared", name)),
+                        message: "unknown type".to_owned(),
+                    });
+
+                    ty.clone()
+                }
+            },
+            TypeValue::Collection(element) => {
+                Type::collection(ty.span, self.resolve_type(element, environment))
+            }
+            TypeValue::Set(element) => Type::set(ty.span, self.resolve_type(element, environment)),
+            TypeValue::Function {
+                parameters,
+                parameter_names,
+                return_type,
+            } => {
+                let resolved = Type::function(
+                    ty.span,
+                    parameters
+                        .iter()
+                        .map(|p| self.resolve_type(p, environment))
+                        .collect(),
+                    self.resolve_type(return_type, environment),
+                );
+
+                match parameter_names {
+                    Some(names) => resolved.parameter_names(names.clone()),
+                    None => resolved,
+                }
+            }
+            _ => ty.clone(),
         }
     }
 
@@ -299,38 +1001,72 @@ impl<'ast> TypeChecker {
                 // }
                 self.expect_types(
                     left,
-                    &[TypeValue::Integer, TypeValue::Decimal],
+                    NUMERIC_TYPES,
                     "left side must be a numeric type".into(),
                 );
 
                 self.expect_types(
                     right,
-                    &[TypeValue::Integer, TypeValue::Decimal],
+                    NUMERIC_TYPES,
                     "right side must be a numeric type".into(),
                 );
             }
             BinaryOperator::Equality | BinaryOperator::Inequality => {
-                // TODO: Implement equality and inequality
+                self.expect_types(
+                    left,
+                    EQUATABLE_TYPES,
+                    "left side must be a comparable type".into(),
+                );
+
+                self.expect_types(
+                    right,
+                    EQUATABLE_TYPES,
+                    "right side must be a comparable type".into(),
+                );
+            }
+            BinaryOperator::And | BinaryOperator::Or => {
+                self.expect_type(left, TypeValue::Boolean, "left side must be boolean".into());
+
+                self.expect_type(
+                    right,
+                    TypeValue::Boolean,
+                    "right side must be boolean".into(),
+                );
             }
-            _ => todo!("expect_allowed_binary_operation: {:?}", operator),
         }
     }
 
     fn expect_match(&mut self, left: &Type<'ast>, right: &Type<'ast>, message: String) {
-        if left != right {
+        if !left.unifies_with(right) {
             let mut labels = vec![];
             labels.extend(left.label(format!("{}", left)));
             labels.extend(right.label(format!("{}", right)));
 
+            let widths = (
+                left.base_type().value.bits(),
+                right.base_type().value.bits(),
+            );
+            let help = if let (Some(left_bits), Some(right_bits)) = widths {
+                format!(
+                    "integer widths differ, found a {}-bit integer (`{}`) and a {}-bit integer (`{}`); declare both with the same width, as there are no casts between them yet",
+                    left_bits,
+                    left.verbose(),
+                    right_bits,
+                    right.verbose(),
+                )
+            } else {
+                format!(
+                    "type mismatch, expected types to be equivalent, but found {} and {}",
+                    left, right
+                )
+            };
+
             self.errors.push(MietteDiagnostic {
                 code: None,
                 severity: None,
                 url: None,
                 labels: Some(labels),
-                help: Some(format!(
-                    "type mismatch, expected types to be equivalent, but found {} and {}",
-                    left, right
-                )),
+                help: Some(help),
                 message,
             });
         }
@@ -341,7 +1077,7 @@ impl<'ast> TypeChecker {
     }
 
     fn expect_types(&mut self, ty: &Type<'ast>, expected: &[TypeValue], message: String) {
-        if !expected.iter().any(|ex| ty.value == *ex) {
+        if !expected.contains(&ty.base_type().value) {
             let mut labels = vec![];
             labels.extend(ty.label(format!("{}", ty)));
 
@@ -363,4 +1099,86 @@ impl<'ast> TypeChecker {
             });
         }
     }
+
+    fn expect_discardable(&mut self, value: &TypedExpression<'ast>) {
+        if value.ty.base_type().value == TypeValue::Unit {
+            return;
+        }
+
+        self.warnings.push(MietteDiagnostic {
+            code: None,
+            severity: Some(Severity::Warning),
+            url: None,
+            labels: Some(vec![value.label(format!("{} is discarded", value.ty))]),
+            help: Some("assign the result to `_` if discarding it is intentional".into()),
+            message: "unused result".to_owned(),
+        });
+    }
+
+    fn expect_integer_literal_fits(
+        &mut self,
+        expression: &Expression<'ast>,
+        expected: &Type<'ast>,
+    ) {
+        let Some(literal) = integer_literal_value(expression) else {
+            return;
+        };
+
+        let fits = match expected.base_type().value {
+            TypeValue::Integer => i32::try_from(literal).is_ok(),
+            _ => true,
+        };
+
+        if !fits {
+            self.errors.push(MietteDiagnostic {
+                code: None,
+                severity: None,
+                url: None,
+                labels: Some(vec![expression.label(format!("{}", literal))]),
+                help: Some(format!("{} does not fit in {}", literal, expected)),
+                message: "integer literal out of range".to_owned(),
+            });
+        }
+    }
+}
+
+/// The type of an integer literal that does not fit in an `i32` is `i64`, when nothing else
+/// decides its type. Other values keep their type.
+fn widen_integer_literal<'ast>(value: &Expression<'ast>, ty: Type<'ast>) -> Type<'ast> {
+    match integer_literal_value(value) {
+        Some(literal) if i32::try_from(literal).is_err() => Type::long(ty.span),
+        _ => ty,
+    }
+}
+
+/// The value of an integer literal expression, looking through groups and negation.
+fn integer_literal_value(expression: &Expression<'_>) -> Option<i64> {
+    match &expression.value {
+        ExpressionValue::Primitive(Primitive::Integer(value)) => Some(*value),
+        ExpressionValue::Group(inner) => integer_literal_value(inner),
+        ExpressionValue::Unary {
+            operator: UnaryOperator::Negative,
+            operand,
+        } => integer_literal_value(operand).map(|value| -value),
+        _ => None,
+    }
+}
+
+/// Collects the names of the types `ty` refers to by name.
+fn type_symbols<'ast>(ty: &Type<'ast>, symbols: &mut Vec<Cow<'ast, str>>) {
+    match &ty.value {
+        TypeValue::Symbol(name) => symbols.push(name.clone()),
+        TypeValue::Collection(element) | TypeValue::Set(element) => type_symbols(element, symbols),
+        TypeValue::Function {
+            parameters,
+            return_type,
+            ..
+        } => {
+            for parameter in parameters {
+                type_symbols(parameter, symbols);
+            }
+            type_symbols(return_type, symbols);
+        }
+        _ => {}
+    }
 }
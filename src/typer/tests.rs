@@ -1,6 +1,11 @@
-use crate::{lexer::Lexer, parser::Parser};
+use crate::{
+    ast::{ExpressionValue, Primitive, StatementValue},
+    lexer::Lexer,
+    parser::Parser,
+};
 
-use super::TypeChecker;
+use super::{entry_point, TypeChecker};
+use miette::{MietteDiagnostic, Severity};
 
 #[test]
 fn basic_type() {
@@ -10,5 +15,967 @@ fn basic_type() {
     }
     "#;
 
-    todo!()
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn integer_literal_as_long() {
+    let code = r#"
+    fn main() {
+      let x ~ i64 = 5;
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn integer_literal_as_integer() {
+    let code = r#"
+    fn main() {
+      let y ~ i32 = 5;
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn integer_literal_out_of_range() {
+    let code = r#"
+    fn main() {
+      let y ~ i32 = 3000000000;
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "integer literal out of range");
+}
+
+#[test]
+fn suffixed_integer_literals() {
+    let code = r#"
+    fn main() {
+      let a ~ i64 = 5l;
+      let b ~ i32 = 5i;
+      let c = 5l;
+      let d ~ i64 = c;
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn suffixed_integer_mismatch() {
+    let code = r#"
+    fn main() {
+      let a ~ i32 = 5l;
+      let b ~ i64 = 5i;
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].help.as_ref().unwrap().contains("64-bit"));
+    assert!(errors[1].help.as_ref().unwrap().contains("32-bit"));
+}
+
+#[test]
+fn function_return_width() {
+    let code = r#"
+    fn long() {
+      5l
+    }
+
+    fn integer() -> i32 {
+      5
+    }
+
+    fn main() {
+      let a ~ i64 = long();
+      let b ~ i64 = integer();
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "the value must match the declared type");
+}
+
+#[test]
+fn typeof_intrinsic() {
+    let code = r#"
+    fn main() {
+      let sum ~ str = typeof(1 + 2);
+      let long ~ str = typeof(len("som"));
+    }
+    "#;
+
+    let module = Parser::new(Lexer::new(code)).parse().unwrap();
+    let modules = TypeChecker::new().type_check(vec![module]).unwrap();
+
+    let body = match &modules[0].definitions[0].value {
+        StatementValue::Function { body, .. } => body,
+        _ => panic!("expected a function"),
+    };
+
+    let statements = match &body.value {
+        ExpressionValue::Group(block) => match &block.value {
+            ExpressionValue::Block { statements, .. } => statements,
+            _ => panic!("expected a block"),
+        },
+        _ => panic!("expected a group"),
+    };
+
+    let values = statements
+        .iter()
+        .map(|statement| match &statement.value {
+            StatementValue::Assignment { value, .. } => value.value.clone(),
+            _ => panic!("expected an assignment"),
+        })
+        .collect::<Vec<_>>();
+
+    assert!(matches!(
+        &values[0],
+        ExpressionValue::Primitive(Primitive::String(name)) if name == "i32"
+    ));
+    assert!(matches!(
+        &values[1],
+        ExpressionValue::Primitive(Primitive::String(name)) if name == "i64"
+    ));
+}
+
+#[test]
+fn static_assert_type() {
+    let code = r#"
+    fn main() {
+      static_assert_type(1 + 2, "i32");
+      static_assert_type(5l, "i32");
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "type assertion failed");
+    assert_eq!(
+        errors[0].help.as_deref(),
+        Some("expected `i32`, found `i64`")
+    );
+}
+
+#[test]
+fn string_length() {
+    let code = r#"
+    fn main() {
+      let n ~ i64 = len("hello");
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn string_length_of_non_string() {
+    let code = r#"
+    fn main() {
+      let n = len(5);
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors[0].message, "argument and parameter must match");
+}
+
+#[test]
+fn string_byte_at() {
+    let code = r#"
+    fn main() {
+      let c ~ char = byte_at("hello", 1);
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn string_equality() {
+    let code = r#"
+    fn main() {
+      let equal ~ bool = "som" == "som";
+      let different ~ bool = "som" != "mos";
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+
+    let errors = type_check(r#"fn main() { let equal ~ bool = "som" == 'c'; }"#).unwrap_err();
+    assert_eq!(errors[0].message, "left and right must be of the same type");
+
+    let errors = type_check("fn f() {} fn main() { let equal ~ bool = f == f; }").unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].message, "left side must be a comparable type");
+}
+
+#[test]
+fn type_alias() {
+    let code = r#"
+    type Celsius = i32;
+
+    fn main() {
+      let c ~ Celsius = 5;
+      let i ~ i32 = c + 1;
+    }
+
+    fn warm(c ~ Celsius) {
+      let i ~ i32 = c;
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn type_alias_mismatch() {
+    let code = r#"
+    type Celsius = i32;
+    type Name = str;
+
+    fn main() {
+      let c ~ Celsius = 5;
+      let n ~ Name = c;
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+
+    let help = errors[0].help.as_ref().unwrap();
+    assert!(help.contains("`Celsius`"));
+    assert!(help.contains("`Name`"));
+}
+
+#[test]
+fn unknown_type() {
+    let code = r#"
+    fn main() {
+      let c ~ Celsius = 5;
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors[0].message, "unknown type");
+}
+
+#[test]
+fn if_without_else() {
+    let code = r#"
+    fn main() {
+      let a = 1;
+      if a > 0 {
+        let b = a;
+      }
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn if_without_else_with_value() {
+    let code = r#"
+    fn main() {
+      let a = 1;
+      if a > 0 {
+        a
+      }
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message,
+        "an if without an else cannot produce a value"
+    );
+}
+
+#[test]
+fn recursive_lambda() {
+    let code = r#"
+    fn main() {
+      let factorial = |n ~ i32| -> i32 1 if n < 2 else n * recurse(n - 1);
+      let result ~ i32 = factorial(5);
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn nested_recursive_lambdas() {
+    let code = r#"
+    fn main() {
+      let count = |n ~ i32| -> i32 0 if n < 1 else (|m ~ i32| -> i32 recurse(m - 1))(n);
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn recursive_lambda_with_user_recurse() {
+    for code in [
+        "fn main() { let recurse = 1; let f = |n ~ i32| -> i32 n + recurse; }",
+        "fn main() { let f = |recurse ~ i32| -> i32 recurse; }",
+    ] {
+        let errors = type_check(code).unwrap_err();
+        assert_eq!(errors.len(), 1, "{code}");
+        assert_eq!(errors[0].message, "`recurse` is already declared");
+    }
+
+    let code = "fn main() { let recurse = 1; let f = |n ~ i32| n + recurse; }";
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn recursive_lambda_without_return_type() {
+    let code = r#"
+    fn main() {
+      let factorial = |n ~ i32| 1 if n < 2 else n * recurse(n - 1);
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors[0].message, "undeclared variable");
+}
+
+#[test]
+fn function_types_unify() {
+    let code = r#"
+    fn main() {
+      let a = 1;
+      let exact = (|n ~ i32| -> i64 5) if a < 2 else (|m ~ i32| -> i64 6);
+      let literal = (|n ~ i32| 1) if a < 2 else (|n ~ i32| -> i64 2);
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn function_types_with_different_arity() {
+    let code = r#"
+    fn main() {
+      let a = 1;
+      let f = (|n ~ i32| 1) if a < 2 else (|n ~ i32, m ~ i32| 2);
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn group_span() {
+    let code = r#"
+    fn main() {
+      let a ~ bool = (1 + 2);
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    let labels = errors[0].labels.as_ref().unwrap();
+
+    let group = "(1 + 2)";
+    let offset = code.find(group).unwrap();
+    assert!(labels
+        .iter()
+        .any(|label| label.offset() == offset && label.len() == group.len()));
+}
+
+#[test]
+fn block_span() {
+    let code = "fn f() -> i32 {\n  let a = 1;\n  \"x\"\n}";
+
+    let errors = type_check(code).unwrap_err();
+    let labels = errors[0].labels.as_ref().unwrap();
+
+    let value = "\"x\"";
+    let offset = code.find(value).unwrap();
+    assert!(labels
+        .iter()
+        .any(|label| label.offset() == offset && label.len() == value.len()));
+}
+
+#[test]
+fn block_types() {
+    let code = r#"
+    fn main() {
+      let empty ~ () = {};
+      let statements ~ () = { let b = 1; };
+      let value ~ i32 = { let b = 1; b };
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn block_without_value() {
+    let code = r#"
+    fn main() {
+      let a ~ i32 = { let b = 1; };
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "the value must match the declared type");
+}
+
+#[test]
+fn function_call() {
+    let code = r#"
+    fn double(n ~ i32) -> i32 {
+      n * 2
+    }
+
+    fn main() {
+      let a ~ i32 = double(4);
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn mutually_recursive_functions() {
+    let code = r#"
+    fn main() {
+      let even ~ bool = is_even(4);
+    }
+
+    fn is_even(n ~ i32) -> bool {
+      true if n < 1 else is_odd(n - 1)
+    }
+
+    fn is_odd(n ~ i32) -> bool {
+      false if n < 1 else is_even(n - 1)
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn function_used_before_declaration_mismatch() {
+    let code = r#"
+    fn main() {
+      let even ~ i32 = is_even(4);
+    }
+
+    fn is_even(n ~ i32) -> bool {
+      n == 0
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "the value must match the declared type");
+}
+
+#[test]
+fn hoisted_function_redeclaration() {
+    let code = r#"
+    fn helper() -> i32 { 1 }
+    fn helper() -> i32 { 2 }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "`helper` is already declared");
+}
+
+#[test]
+fn function_redeclaration() {
+    let code = r#"
+    fn helper() {}
+    fn helper() {}
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "`helper` is already declared");
+    assert_eq!(errors[0].labels.as_ref().unwrap().len(), 2);
+}
+
+#[test]
+fn function_shadowing() {
+    let code = r#"
+    fn helper() {}
+
+    fn main() {
+      fn helper() {};
+      helper()
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn function_shadowing_value() {
+    let code = r#"
+    fn main() {
+      let helper = 1;
+      fn helper() {};
+      helper()
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn incorrect_number_of_arguments() {
+    let code = r#"
+    fn add(x ~ i32, y ~ i32) -> i32 {
+      x + y
+    }
+
+    fn main() {
+      add(1)
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors[0].message, "incorrect number of arguments");
+    assert_eq!(
+        errors[0].help.as_deref(),
+        Some("fn(x ~ i32, y ~ i32) -> i32 expects 2 arguments, but found 1")
+    );
+}
+
+#[test]
+fn unknown_module_in_path() {
+    let code = r#"
+    fn main() {
+      std::io::print(1)
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors[0].message, "unknown module `std`");
+
+    let labels = errors[0].labels.as_ref().unwrap();
+    assert_eq!(labels[0].offset(), code.find("std").unwrap());
+    assert_eq!(labels[0].len(), "std".len());
+}
+
+#[test]
+fn main_returns_exit_code() {
+    for code in [
+        "fn main() {}",
+        "fn main() { 1 }",
+        "fn main() -> i32 { 0 }",
+        "type Code = i32; fn main() -> Code { 0 }",
+    ] {
+        assert!(type_check(code).is_ok(), "{}", code);
+    }
+}
+
+#[test]
+fn main_returns_other_type() {
+    for code in [
+        "fn main() { 5l }",
+        "fn main() -> bool { true }",
+        r#"fn main() { "error" }"#,
+    ] {
+        let errors = type_check(code).unwrap_err();
+        assert_eq!(errors.len(), 1, "{}", code);
+        assert_eq!(errors[0].message, "`main` must return nothing or an `i32`");
+    }
+}
+
+#[test]
+fn main_exit_code_out_of_range() {
+    let errors = type_check("fn main() { 3000000000 }").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "integer literal out of range");
+}
+
+#[test]
+fn main_with_parameters() {
+    let errors = type_check("fn main(argc ~ i32) {}").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "`main` cannot take parameters");
+}
+
+#[test]
+fn entry_point_in_any_module() {
+    let modules = vec![
+        parse_module("helpers", "fn helper() {}"),
+        parse_module("app", "fn main() { helper() }"),
+    ];
+
+    let main = entry_point(&modules).unwrap();
+    assert_eq!(main.name, "main");
+}
+
+#[test]
+fn entry_point_missing() {
+    let modules = vec![parse_module("helpers", "fn helper() {}")];
+
+    let errors = entry_point(&modules).unwrap_err();
+    assert_eq!(errors[0].message, "no `main` function found");
+}
+
+#[test]
+fn entry_point_ambiguous() {
+    let modules = vec![
+        parse_module("first", "fn main() {}"),
+        parse_module("second", "fn main() {}"),
+    ];
+
+    let errors = entry_point(&modules).unwrap_err();
+    assert_eq!(errors[0].message, "multiple `main` functions found");
+    assert_eq!(errors[0].labels.as_ref().unwrap().len(), 2);
+
+    let help = errors[0].help.as_ref().unwrap();
+    assert!(help.contains("`first`"));
+    assert!(help.contains("`second`"));
+}
+
+fn parse_module<'ast>(
+    name: &'ast str,
+    code: &'ast str,
+) -> crate::ast::Module<'ast, crate::ast::Expression<'ast>> {
+    let mut module = Parser::new(Lexer::new(code))
+        .parse()
+        .expect("code should parse");
+    module.name = name.into();
+    module
+}
+
+#[test]
+fn unused_variable() {
+    let warnings = warnings(
+        r#"
+    fn main() {
+      let a = 1;
+      let _b = 2;
+    }
+    "#,
+    );
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].message, "unused variable `a`");
+    assert_eq!(warnings[0].severity, Some(Severity::Warning));
+    assert!(warnings[0].help.as_ref().unwrap().contains("`_a`"));
+}
+
+#[test]
+fn used_variable() {
+    let warnings = warnings(
+        r#"
+    fn main() {
+      let a = 1;
+      let b = { let a = 2; a };
+      b + a
+    }
+    "#,
+    );
+
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn shadowed_variable() {
+    let warnings = warnings(
+        r#"
+    fn twice(a ~ i32) -> i32 {
+      a * 2
+    }
+
+    fn main() {
+      let a = 1;
+      let f = |a ~ i32| a;
+      f(2)
+    }
+    "#,
+    );
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].message, "unused variable `a`");
+}
+
+#[test]
+fn discarded_result() {
+    let warnings = warnings(
+        r#"
+    fn double(n ~ i32) -> i32 {
+      n * 2
+    }
+
+    fn nothing() {}
+
+    fn main() {
+      double(2);
+      nothing();
+    }
+    "#,
+    );
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].message, "unused result");
+}
+
+fn warnings(code: &str) -> Vec<MietteDiagnostic> {
+    let module = Parser::new(Lexer::new(code))
+        .parse()
+        .expect("code should parse");
+
+    let mut type_checker = TypeChecker::new();
+    type_checker
+        .type_check(vec![module])
+        .expect("code should type check");
+    type_checker.warnings().to_vec()
+}
+
+#[test]
+fn struct_not_implemented() {
+    let errors = type_check("struct Point: x ~ i32, y ~ i32; fn main() {}").unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "feature not yet implemented");
+    assert_eq!(
+        errors[0].help.as_deref(),
+        Some("structs cannot be type checked yet")
+    );
+}
+
+#[test]
+fn type_parameter_in_scope() {
+    let code = r#"
+    fn id<T>(x ~ T) -> T {
+      let y ~ T = x;
+      y
+    }
+
+    fn main() {}
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn type_parameter_out_of_scope() {
+    let code = r#"
+    fn id<T>(x ~ T) -> T { x }
+
+    fn main() {
+      let y ~ T = 1;
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert!(errors.iter().any(|error| error.message == "unknown type"));
+}
+
+#[test]
+fn infer_type_arguments() {
+    let code = r#"
+    fn id<T>(x ~ T) -> T { x }
+
+    fn main() {
+      let a ~ i32 = id(5);
+      let b ~ bool = id(true);
+      let c ~ i64 = id(5000000000);
+      a
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn inferred_type_argument_mismatch() {
+    let code = r#"
+    fn id<T>(x ~ T) -> T { x }
+
+    fn main() {
+      let _b ~ bool = id(5);
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn uninferable_type_argument() {
+    let code = r#"
+    fn make<T>(x ~ i32) -> T { make(x) }
+
+    fn main() {}
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "cannot infer type parameter `T`");
+}
+
+#[test]
+fn unknown_attribute() {
+    let warnings = warnings("#[test] #[inlined] fn main() {}");
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].message, "unknown attribute `inlined`");
+}
+
+#[test]
+fn interpolated_string() {
+    let code = r#"
+    fn show(a ~ i32, b ~ bool) -> str { "a = ${a + 1}, b = ${b}" }
+
+    fn main() {}
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn interpolate_function() {
+    let code = r#"
+    fn show() -> str { "${show}" }
+
+    fn main() {}
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "cannot interpolate this value");
+}
+
+#[test]
+fn mismatch_describes_types() {
+    let code = r#"
+    fn main() {
+      let _a ~ [[i32]] = 1;
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    let labels = errors[0]
+        .labels
+        .iter()
+        .flatten()
+        .filter_map(|label| label.label())
+        .collect::<Vec<_>>();
+
+    assert_eq!(labels, ["a list of lists of 32-bit integers", "an integer"]);
+}
+
+#[test]
+fn integer_width_mismatch() {
+    let code = r#"
+    fn main() {
+      let a ~ i32 = 1;
+      let _b ~ i64 = a;
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    let help = errors[0].help.as_deref().unwrap();
+
+    assert_eq!(errors.len(), 1);
+    assert!(help.contains("a 64-bit integer (`i64`) and a 32-bit integer (`i32`)"));
+}
+
+#[test]
+fn deeply_nested_expression() {
+    let code = format!("fn main() {{ {}1{} }}", "(".repeat(8), ")".repeat(8));
+    let module = Parser::new(Lexer::new(&code)).parse().unwrap();
+
+    let errors = TypeChecker::new()
+        .with_max_depth(4)
+        .type_check(vec![module])
+        .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "expression nesting too deep");
+}
+
+fn type_check(code: &str) -> crate::Result<()> {
+    let lexer = Lexer::new(code);
+    let mut parser = Parser::new(lexer);
+    let module = parser.parse().expect("code should parse");
+
+    TypeChecker::new().type_check(vec![module]).map(|_| ())
+}
+
+#[test]
+fn type_alias_declared_later() {
+    let code = r#"
+    type Temperature = Celsius;
+    type Celsius = i32;
+
+    fn main() {
+      let t ~ Temperature = 5;
+      let i ~ i32 = t;
+    }
+    "#;
+
+    assert!(type_check(code).is_ok());
+}
+
+#[test]
+fn recursive_type_alias() {
+    let code = r#"
+    type A = B;
+    type B = A;
+
+    fn main() {}
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|error| error.message == "type alias `A` is recursive"));
+}
+
+#[test]
+fn dangling_type_alias() {
+    let code = r#"
+    type Temperature = Kelvin;
+
+    fn main() {}
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "unknown type");
+}
+
+#[test]
+fn block_declarations_stay_in_block() {
+    let code = r#"
+    fn main() {
+      let a ~ i32 = 1;
+      let b = { let a ~ bool = true; a };
+      let c ~ i32 = a;
+      let d = inner;
+    }
+
+    fn nested() {
+      { let inner = 1; inner };
+    }
+    "#;
+
+    let errors = type_check(code).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "undeclared variable");
+    assert_eq!(errors[0].help.as_deref(), Some("inner is not declared"));
 }
@@ -0,0 +1,174 @@
+use crate::ast::{Expression, ExpressionValue, Module, Primitive, Statement, StatementValue};
+use miette::{LabeledSpan, MietteDiagnostic, Severity, SourceSpan};
+use std::borrow::Cow;
+
+/// Warns about `let` bindings that are never read. Bindings starting with `_` are exempt.
+pub fn unused_variables(module: &Module<'_, Expression<'_>>) -> Vec<MietteDiagnostic> {
+    let mut usage = Usage::default();
+
+    usage.enter();
+    for statement in &module.definitions {
+        usage.statement(statement);
+    }
+    usage.exit();
+
+    usage.warnings
+}
+
+struct Binding<'ast> {
+    name: Cow<'ast, str>,
+    // Parameters shadow bindings but are not reported
+    span: Option<SourceSpan>,
+    used: bool,
+}
+
+#[derive(Default)]
+struct Usage<'ast> {
+    scopes: Vec<Vec<Binding<'ast>>>,
+    warnings: Vec<MietteDiagnostic>,
+}
+
+impl<'ast> Usage<'ast> {
+    fn enter(&mut self) {
+        self.scopes.push(vec![]);
+    }
+
+    fn exit(&mut self) {
+        let scope = self.scopes.pop().unwrap_or_default();
+
+        for binding in scope {
+            let span = match binding.span {
+                Some(span) if !binding.used && !binding.name.starts_with('_') => span,
+                _ => continue,
+            };
+
+            self.warnings.push(MietteDiagnostic {
+                code: None,
+                severity: Some(Severity::Warning),
+                url: None,
+                labels: Some(vec![LabeledSpan::at(span, "never used")]),
+                help: Some(format!(
+                    "if this is intentional, prefix it with an underscore: `_{}`",
+                    binding.name
+                )),
+                message: format!("unused variable `{}`", binding.name),
+            });
+        }
+    }
+
+    fn declare(&mut self, name: Cow<'ast, str>, span: Option<SourceSpan>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Binding {
+                name,
+                span,
+                used: false,
+            });
+        }
+    }
+
+    fn read(&mut self, name: &str) {
+        let binding = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.iter_mut().rev().find(|b| b.name == name));
+
+        if let Some(binding) = binding {
+            binding.used = true;
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement<'ast, Expression<'ast>>) {
+        match &statement.value {
+            StatementValue::Block(statements) => {
+                self.enter();
+                for statement in statements {
+                    self.statement(statement);
+                }
+                self.exit();
+            }
+            StatementValue::Expression(expression) | StatementValue::Return(expression) => {
+                self.expression(expression)
+            }
+            StatementValue::Assignment { name, value, .. } => {
+                self.expression(value);
+                self.declare(name.clone(), Some(statement.span));
+            }
+            StatementValue::Function { header, body } => {
+                self.enter();
+                for parameter in &header.parameters {
+                    self.declare(parameter.name.clone(), None);
+                }
+                self.expression(body);
+                self.exit();
+            }
+            StatementValue::Conditional {
+                condition,
+                truthy,
+                falsy,
+            } => {
+                self.expression(condition);
+                self.statement(truthy);
+                if let Some(falsy) = falsy {
+                    self.statement(falsy);
+                }
+            }
+            StatementValue::Struct { .. }
+            | StatementValue::Enum { .. }
+            | StatementValue::Trait { .. }
+            | StatementValue::TypeAlias { .. } => {}
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression<'ast>) {
+        match &expression.value {
+            ExpressionValue::Primitive(Primitive::Identifier(name)) => self.read(name),
+            ExpressionValue::Primitive(_) | ExpressionValue::Path(_) => {}
+            ExpressionValue::Binary { left, right, .. } => {
+                self.expression(left);
+                self.expression(right);
+            }
+            ExpressionValue::Unary { operand, .. } => self.expression(operand),
+            ExpressionValue::Group(expression) => self.expression(expression),
+            ExpressionValue::Block {
+                statements,
+                return_value,
+            } => {
+                self.enter();
+                for statement in statements {
+                    self.statement(statement);
+                }
+                self.expression(return_value);
+                self.exit();
+            }
+            ExpressionValue::Conditional {
+                condition,
+                truthy,
+                falsy,
+            } => {
+                self.expression(condition);
+                self.expression(truthy);
+                self.expression(falsy);
+            }
+            ExpressionValue::Call { callee, arguments } => {
+                self.expression(callee);
+                for argument in arguments {
+                    self.expression(argument);
+                }
+            }
+            ExpressionValue::Lambda(lambda) => {
+                self.enter();
+                for parameter in &lambda.parameters {
+                    self.declare(parameter.name.clone(), None);
+                }
+                self.expression(&lambda.body);
+                self.exit();
+            }
+            ExpressionValue::Interpolation(parts) => {
+                for part in parts {
+                    self.expression(part);
+                }
+            }
+        }
+    }
+}